@@ -1,9 +1,9 @@
-use anyhow::Result;
-use std::net::TcpListener;
+use anyhow::{Error, Result};
 
-use cynthia::runtime::{self, future, Async};
+use cynthia::runtime::future;
 
 use nephele::http_types::{Request, Response, StatusCode};
+use nephele::proto::Server;
 use nephele::tls::{Identity, TlsAcceptor};
 
 async fn serve(req: Request) -> nephele::common::http_types::Result<Response> {
@@ -15,55 +15,18 @@ async fn serve(req: Request) -> nephele::common::http_types::Result<Response> {
     Ok(res)
 }
 
-async fn listen(listener: Async<TcpListener>, tls: Option<TlsAcceptor>) -> Result<()> {
-    let host = match &tls {
-        None => format!("http://{}", listener.get_ref().local_addr()?),
-        Some(_) => format!("https://{}", listener.get_ref().local_addr()?),
-    };
-    println!("Listening on {}", host);
-
-    loop {
-        let (stream, _) = listener.accept().await?;
-        let task = match &tls {
-            None => {
-                let stream = runtime::dup::Arc::new(stream);
-                runtime::spawn(async move {
-                    if let Err(err) = nephele::proto::h1::accept(stream, serve).await {
-                        println!("Connection error: {:#?}", err);
-                    }
-                })
-            }
-            Some(tls) => match tls.accept(stream).await {
-                Ok(stream) => {
-                    let stream = runtime::dup::Arc::new(runtime::dup::Mutex::new(stream));
-                    runtime::spawn(async move {
-                        if let Err(err) = nephele::proto::h1::accept(stream, serve).await {
-                            println!("Connection error: {:#?}", err);
-                        }
-                    })
-                }
-                Err(err) => {
-                    println!("Failed to establish secure TLS connection: {:#?}", err);
-                    continue;
-                }
-            },
-        };
-
-        task.detach();
-    }
-}
-
 #[cynthia::main]
 async fn main() -> Result<()> {
     let identity = Identity::from_pkcs12(include_bytes!("identity.pfx"), "password")?;
     let tls = TlsAcceptor::from(native_tls::TlsAcceptor::new(identity)?);
 
-    let listener = Async::<TcpListener>::bind("127.0.0.1:7000").await?;
-    let http = listen(listener, None);
-    let https = listen(
-        Async::<TcpListener>::bind("127.0.0.1:8002").await?,
-        Some(tls),
-    );
-    future::try_zip(http, https).await?;
+    let http = Server::bind("127.0.0.1:7000").await?;
+    let https = Server::bind("127.0.0.1:8002").await?.tls(tls);
+    println!("Listening on http://{}", http.local_addr()?);
+    println!("Listening on https://{}", https.local_addr()?);
+
+    future::try_zip(http.serve(serve), https.serve(serve))
+        .await
+        .map_err(Error::msg)?;
     Ok(())
 }
@@ -5,11 +5,12 @@ use url::Url;
 use cynthia::runtime::{self, future, Async};
 
 use nephele::http_types::{convert::json, Body, Method, Request, Response, StatusCode};
+use nephele::server::middleware::{AccessLog, Builder, CatchPanic, SetRequestId};
+use nephele::server::{BoxFuture, Params, Router};
 use nephele::tls::{Identity, TlsAcceptor};
 
 static INDEX_STR: &[u8] = b"<a href=\"test.html\">test.html</a>";
 static INTERNAL_SERVER_ERROR: &[u8] = b"Internal Server Error";
-static NOTFOUND_STR: &[u8] = b"Not Found";
 static UPSTREAM_URL: &str = "http://127.0.0.1:7000/json_api";
 
 async fn api_fetch(_req: Request) -> nephele::common::http_types::Result<Response> {
@@ -71,26 +72,35 @@ async fn api_get_json() -> nephele::common::http_types::Result<Response> {
     }
 }
 
-async fn serve(req: Request) -> nephele::common::http_types::Result<Response> {
-    match (req.method(), req.url().path()) {
-        (Method::Get, "/") | (Method::Get, "/index.html") => {
-            let mut res = Response::new(StatusCode::Ok);
-            res.set_body(Body::from_bytes(INDEX_STR.into()));
-            Ok(res)
-        }
-        (Method::Get, "/fetch.html") => api_fetch(req).await,
-        (Method::Get, "/fetch_upstream") => api_fetch_upstream().await,
-        (Method::Post, "/json_api") => api_post_json(req).await,
-        (Method::Get, "/json_api") => api_get_json().await,
-        _ => {
-            let mut res = Response::new(StatusCode::NotFound);
-            res.set_body(Body::from_bytes(NOTFOUND_STR.into()));
-            Ok(res)
-        }
-    }
+async fn index(_req: Request) -> nephele::common::http_types::Result<Response> {
+    let mut res = Response::new(StatusCode::Ok);
+    res.set_body(Body::from_bytes(INDEX_STR.into()));
+    Ok(res)
 }
 
-async fn listen(listener: Async<TcpListener>, tls: Option<TlsAcceptor>) -> Result<()> {
+async fn hello(req: Request) -> nephele::common::http_types::Result<Response> {
+    let name = req.ext().get::<Params>().and_then(|p| p.get("name"));
+    let mut res = Response::new(StatusCode::Ok);
+    res.insert_header("Content-Type", "text/plain");
+    res.set_body(format!("hello {}", name.unwrap_or_default()));
+    Ok(res)
+}
+
+fn router() -> Router {
+    Router::new()
+        .get("/", index)
+        .get("/index.html", index)
+        .get("/hello/:name", hello)
+        .get("/fetch.html", api_fetch)
+        .get("/fetch_upstream", |_| api_fetch_upstream())
+        .post("/json_api", api_post_json)
+        .get("/json_api", |_| api_get_json())
+}
+
+async fn listen<F>(listener: Async<TcpListener>, tls: Option<TlsAcceptor>, serve: F) -> Result<()>
+where
+    F: Fn(Request) -> BoxFuture<'static> + Clone + Send + Sync + 'static,
+{
     let host = match &tls {
         None => format!("http://{}", listener.get_ref().local_addr()?),
         Some(_) => format!("https://{}", listener.get_ref().local_addr()?),
@@ -102,6 +112,7 @@ async fn listen(listener: Async<TcpListener>, tls: Option<TlsAcceptor>) -> Resul
         let task = match &tls {
             None => {
                 let stream = runtime::dup::Arc::new(stream);
+                let serve = serve.clone();
                 runtime::spawn(async move {
                     if let Err(err) = nephele::proto::h1::accept(stream, serve).await {
                         println!("Connection error: {:#?}", err);
@@ -111,6 +122,7 @@ async fn listen(listener: Async<TcpListener>, tls: Option<TlsAcceptor>) -> Resul
             Some(tls) => match tls.accept(stream).await {
                 Ok(stream) => {
                     let stream = runtime::dup::Arc::new(runtime::dup::Mutex::new(stream));
+                    let serve = serve.clone();
                     runtime::spawn(async move {
                         if let Err(err) = nephele::proto::h1::accept(stream, serve).await {
                             println!("Connection error: {:#?}", err);
@@ -133,10 +145,20 @@ async fn main() -> Result<()> {
     let identity = Identity::from_pkcs12(include_bytes!("identity.pfx"), "password")?;
     let tls = TlsAcceptor::from(native_tls::TlsAcceptor::new(identity)?);
 
-    let http = listen(Async::<TcpListener>::bind("127.0.0.1:7000").await?, None);
+    let serve = Builder::new()
+        .with(CatchPanic::new())
+        .with(AccessLog::new())
+        .with(SetRequestId::new())
+        .endpoint(router().into_endpoint());
+    let http = listen(
+        Async::<TcpListener>::bind("127.0.0.1:7000").await?,
+        None,
+        serve.clone(),
+    );
     let https = listen(
         Async::<TcpListener>::bind("127.0.0.1:8002").await?,
         Some(tls),
+        serve,
     );
     future::try_zip(http, https).await?;
     Ok(())
@@ -0,0 +1,65 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthenticationScheme {
+    Basic,
+    Bearer,
+    Digest,
+    Hoba,
+    Mutual,
+    Negotiate,
+    OAuth,
+    ScramSha1,
+    ScramSha256,
+    Vapid,
+    Other(String),
+}
+
+impl AuthenticationScheme {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Basic => "Basic",
+            Self::Bearer => "Bearer",
+            Self::Digest => "Digest",
+            Self::Hoba => "HOBA",
+            Self::Mutual => "Mutual",
+            Self::Negotiate => "Negotiate",
+            Self::OAuth => "OAuth",
+            Self::ScramSha1 => "SCRAM-SHA-1",
+            Self::ScramSha256 => "SCRAM-SHA-256",
+            Self::Vapid => "vapid",
+            Self::Other(scheme) => scheme,
+        }
+    }
+}
+
+impl Display for AuthenticationScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AuthenticationScheme {
+    type Err = crate::common::http_types::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let scheme = match s.to_ascii_lowercase().as_str() {
+            "basic" => Self::Basic,
+            "bearer" => Self::Bearer,
+            "digest" => Self::Digest,
+            "hoba" => Self::Hoba,
+            "mutual" => Self::Mutual,
+            "negotiate" => Self::Negotiate,
+            "oauth" => Self::OAuth,
+            "scram-sha-1" => Self::ScramSha1,
+            "scram-sha-256" => Self::ScramSha256,
+            "vapid" => Self::Vapid,
+            _ => match crate::common::http_types::parse_utils::parse_token(s) {
+                (Some(token), "") => Self::Other(token.to_string()),
+                _ => crate::bail_status!(400, "Invalid authentication scheme {:?}", s),
+            },
+        };
+        Ok(scheme)
+    }
+}
@@ -0,0 +1,79 @@
+use crate::common::http_types::auth::AuthenticationScheme;
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, AUTHORIZATION};
+use crate::common::http_types::parse_utils::is_field_text;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Authorization {
+    scheme: AuthenticationScheme,
+    credentials: String,
+}
+
+impl Authorization {
+    pub fn new(
+        scheme: AuthenticationScheme,
+        credentials: String,
+    ) -> crate::common::http_types::Result<Self> {
+        crate::ensure!(is_field_text(&credentials), "Invalid credentials");
+        Ok(Self {
+            scheme,
+            credentials,
+        })
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(AUTHORIZATION) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let value = headers.last().as_str().trim();
+        let (scheme, credentials) = match value.split_once(' ') {
+            Some((scheme, credentials)) => (scheme, credentials.trim()),
+            None => (value, ""),
+        };
+        Ok(Some(Self {
+            scheme: scheme.parse()?,
+            credentials: credentials.to_string(),
+        }))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        AUTHORIZATION
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = match self.credentials.is_empty() {
+            true => self.scheme.to_string(),
+            false => format!("{} {}", self.scheme, self.credentials),
+        };
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn scheme(&self) -> &AuthenticationScheme {
+        &self.scheme
+    }
+
+    pub fn set_scheme(&mut self, scheme: AuthenticationScheme) {
+        self.scheme = scheme;
+    }
+
+    pub fn credentials(&self) -> &str {
+        &self.credentials
+    }
+
+    pub fn set_credentials(
+        &mut self,
+        credentials: String,
+    ) -> crate::common::http_types::Result<()> {
+        crate::ensure!(is_field_text(&credentials), "Invalid credentials");
+        self.credentials = credentials;
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use crate::common::http_types::auth::{AuthenticationScheme, Authorization};
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, AUTHORIZATION};
+use crate::common::http_types::Status;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BasicAuth {
+    username: String,
+    password: String,
+}
+
+impl BasicAuth {
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let auth = match Authorization::from_headers(headers)? {
+            Some(auth) => auth,
+            None => return Ok(None),
+        };
+
+        crate::ensure_status!(
+            *auth.scheme() == AuthenticationScheme::Basic,
+            400,
+            "Expected basic auth scheme, found {}",
+            auth.scheme()
+        );
+        Self::from_credentials(auth.credentials()).map(Some)
+    }
+
+    pub fn from_credentials(
+        credentials: impl AsRef<[u8]>,
+    ) -> crate::common::http_types::Result<Self> {
+        let bytes = base64::decode(credentials).status(400)?;
+        let credentials = String::from_utf8(bytes).status(400)?;
+
+        match credentials.split_once(':') {
+            Some((username, password)) => Ok(Self::new(username, password)),
+            None => crate::bail_status!(400, "Missing password in basic auth credentials"),
+        }
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        AUTHORIZATION
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let credentials = base64::encode(format!("{}:{}", self.username, self.password));
+        let output = format!("{} {}", AuthenticationScheme::Basic, credentials);
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+}
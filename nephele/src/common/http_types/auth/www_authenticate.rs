@@ -0,0 +1,96 @@
+use crate::common::http_types::auth::AuthenticationScheme;
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, WWW_AUTHENTICATE};
+use crate::common::http_types::parse_utils::{is_field_text, parse_quoted_string, parse_token};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WwwAuthenticate {
+    scheme: AuthenticationScheme,
+    realm: String,
+}
+
+impl WwwAuthenticate {
+    pub fn new(
+        scheme: AuthenticationScheme,
+        realm: String,
+    ) -> crate::common::http_types::Result<Self> {
+        crate::ensure!(is_field_text(&realm), "Invalid realm {:?}", realm);
+        Ok(Self { scheme, realm })
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(WWW_AUTHENTICATE) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let value = headers.last().as_str().trim();
+        let (scheme, mut rest) = match parse_token(value) {
+            (Some(scheme), rest) => (scheme.parse::<AuthenticationScheme>()?, rest.trim_start()),
+            (None, _) => crate::bail_status!(400, "Invalid WWW-Authenticate {:?}", value),
+        };
+
+        let mut realm = None;
+        while !rest.is_empty() {
+            let (name, after) = match parse_token(rest) {
+                (Some(name), after) => (name, after.trim_start()),
+                (None, _) => crate::bail_status!(400, "Invalid WWW-Authenticate {:?}", value),
+            };
+            let after = match after.strip_prefix('=') {
+                Some(after) => after.trim_start(),
+                None => crate::bail_status!(400, "Invalid WWW-Authenticate {:?}", value),
+            };
+            let (param, after) = match parse_quoted_string(after) {
+                (Some(param), after) => (param.into_owned(), after),
+                (None, _) => match parse_token(after) {
+                    (Some(param), after) => (param.to_string(), after),
+                    (None, _) => crate::bail_status!(400, "Invalid WWW-Authenticate {:?}", value),
+                },
+            };
+            if name.eq_ignore_ascii_case("realm") {
+                realm = Some(param);
+            }
+            rest = after.trim_start().trim_start_matches(',').trim_start();
+        }
+
+        let realm = match realm {
+            Some(realm) => realm,
+            None => crate::bail_status!(400, "Missing realm in WWW-Authenticate {:?}", value),
+        };
+        Ok(Some(Self { scheme, realm }))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        WWW_AUTHENTICATE
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let realm = self.realm.replace('\\', "\\\\").replace('"', "\\\"");
+        let output = format!("{} realm=\"{}\"", self.scheme, realm);
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn scheme(&self) -> &AuthenticationScheme {
+        &self.scheme
+    }
+
+    pub fn set_scheme(&mut self, scheme: AuthenticationScheme) {
+        self.scheme = scheme;
+    }
+
+    pub fn realm(&self) -> &str {
+        &self.realm
+    }
+
+    pub fn set_realm(&mut self, realm: String) -> crate::common::http_types::Result<()> {
+        crate::ensure!(is_field_text(&realm), "Invalid realm {:?}", realm);
+        self.realm = realm;
+        Ok(())
+    }
+}
@@ -1,3 +1,7 @@
+use async_compression::futures::bufread::{
+    BrotliDecoder, BrotliEncoder, GzipDecoder, GzipEncoder, ZlibDecoder, ZlibEncoder,
+};
+use cynthia::fs::File;
 use cynthia::future::{prelude::*, swap};
 use cynthia::ready;
 use serde::{de::DeserializeOwned, Serialize};
@@ -5,6 +9,7 @@ use std::fmt::{self, Debug};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+use crate::common::http_types::content::Encoding;
 use crate::common::http_types::{mime, Mime};
 use crate::common::http_types::{Status, StatusCode};
 
@@ -113,13 +118,12 @@ impl Body {
         Ok(serde_urlencoded::from_str(&s).status(StatusCode::UnprocessableEntity)?)
     }
 
-    #[cfg(all(feature = "fs", not(target_os = "unknown")))]
-    pub async fn from_file<P>(path: P) -> io::Result<Self>
+    pub async fn from_file<P>(path: P) -> std::io::Result<Self>
     where
         P: AsRef<std::path::Path>,
     {
         let path = path.as_ref();
-        let mut file = file::File::open(path).await?;
+        let mut file = File::open(path).await?;
         let len = file.metadata().await?.len();
 
         let mime = peek_mime(&mut file)
@@ -130,7 +134,7 @@ impl Body {
         Ok(Self {
             mime,
             length: Some(len as usize),
-            reader: Box::new(io::BufferReader::new(file)),
+            reader: Box::new(swap::BufferReader::new(file)),
             bytes_read: 0,
         })
     }
@@ -150,6 +154,48 @@ impl Body {
     pub fn set_mime(&mut self, mime: impl Into<Mime>) {
         self.mime = mime.into();
     }
+
+    pub fn compress(self, encoding: Encoding) -> Self {
+        let reader = self.reader;
+        let reader: Box<dyn AsyncBufRead + Unpin + Send + Sync + 'static> = match encoding {
+            Encoding::Gzip => Box::new(swap::BufferReader::new(GzipEncoder::new(reader))),
+            Encoding::Deflate => Box::new(swap::BufferReader::new(ZlibEncoder::new(reader))),
+            Encoding::Brotli => Box::new(swap::BufferReader::new(BrotliEncoder::new(reader))),
+            Encoding::Identity => reader,
+        };
+        let length = match encoding {
+            Encoding::Identity => self.length,
+            _ => None,
+        };
+
+        Self {
+            reader,
+            mime: self.mime,
+            length,
+            bytes_read: 0,
+        }
+    }
+
+    pub fn decompress(self, encoding: Encoding) -> Self {
+        let reader = self.reader;
+        let reader: Box<dyn AsyncBufRead + Unpin + Send + Sync + 'static> = match encoding {
+            Encoding::Gzip => Box::new(swap::BufferReader::new(GzipDecoder::new(reader))),
+            Encoding::Deflate => Box::new(swap::BufferReader::new(ZlibDecoder::new(reader))),
+            Encoding::Brotli => Box::new(swap::BufferReader::new(BrotliDecoder::new(reader))),
+            Encoding::Identity => reader,
+        };
+        let length = match encoding {
+            Encoding::Identity => self.length,
+            _ => None,
+        };
+
+        Self {
+            reader,
+            mime: self.mime,
+            length,
+            bytes_read: 0,
+        }
+    }
 }
 
 impl Debug for Body {
@@ -225,18 +271,16 @@ impl AsyncBufRead for Body {
     }
 }
 
-#[cfg(all(feature = "fs", not(target_os = "unknown")))]
-async fn peek_mime(file: &mut file::File) -> io::Result<Option<Mime>> {
+pub(crate) async fn peek_mime(file: &mut File) -> std::io::Result<Option<Mime>> {
     let mut buf = [0_u8; 300];
-    file.read(&mut buf).await?;
-    let mime = Mime::sniff(&buf).ok();
+    let len = file.read(&mut buf).await?;
+    let mime = Mime::sniff(&buf[..len]).ok();
 
-    file.seek(io::SeekFrom::Start(0)).await?;
+    file.seek(std::io::SeekFrom::Start(0)).await?;
     Ok(mime)
 }
 
-#[cfg(all(feature = "fs", not(target_os = "unknown")))]
-fn guess_ext(path: &std::path::Path) -> Option<Mime> {
+pub(crate) fn guess_ext(path: &std::path::Path) -> Option<Mime> {
     let ext = path.extension().map(|p| p.to_str()).flatten();
     ext.and_then(Mime::from_extension)
 }
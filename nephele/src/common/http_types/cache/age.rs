@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, AGE};
+use crate::common::http_types::Status;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Age {
+    dur: Duration,
+}
+
+impl Age {
+    pub fn new(dur: Duration) -> Self {
+        Self { dur }
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(AGE) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let secs = headers.last().as_str().trim().parse::<u64>().status(400)?;
+        Ok(Some(Self {
+            dur: Duration::from_secs(secs),
+        }))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        AGE
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = self.dur.as_secs().to_string();
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.dur
+    }
+
+    pub fn set_duration(&mut self, dur: Duration) {
+        self.dur = dur;
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt::{self, Display};
+use std::time::Duration;
+
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, CACHE_CONTROL};
+use crate::common::http_types::parse_utils::{
+    is_field_text, is_token, parse_quoted_string, parse_token,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheDirective {
+    Immutable,
+    MaxAge(Duration),
+    MaxStale(Option<Duration>),
+    MinFresh(Duration),
+    MustRevalidate,
+    NoCache(Vec<HeaderName>),
+    NoStore,
+    NoTransform,
+    OnlyIfCached,
+    Private(Vec<HeaderName>),
+    ProxyRevalidate,
+    Public,
+    SMaxAge(Duration),
+    StaleIfError(Duration),
+    StaleWhileRevalidate(Duration),
+    Extension(String, Option<String>),
+}
+
+impl CacheDirective {
+    fn parse(input: &str) -> crate::common::http_types::Result<(Self, &str)> {
+        let (name, rest) = match parse_token(input) {
+            (Some(name), rest) => (name.to_ascii_lowercase(), rest.trim_start()),
+            (None, _) => crate::bail_status!(400, "Invalid cache directive {:?}", input),
+        };
+
+        let (value, rest) = match rest.strip_prefix('=').map(str::trim_start) {
+            Some(value) if value.starts_with('"') => match parse_quoted_string(value) {
+                (Some(value), rest) => (Some(value.into_owned()), rest),
+                (None, _) => crate::bail_status!(400, "Invalid cache directive {:?}", input),
+            },
+            Some(value) => match parse_token(value) {
+                (Some(value), rest) => (Some(value.to_string()), rest),
+                (None, _) => crate::bail_status!(400, "Invalid cache directive {:?}", input),
+            },
+            None => (None, rest),
+        };
+
+        let seconds = |value: Option<String>| -> crate::common::http_types::Result<Duration> {
+            match value.as_deref().map(str::parse::<u64>) {
+                Some(Ok(secs)) => Ok(Duration::from_secs(secs)),
+                _ => crate::bail_status!(400, "Invalid delta-seconds in {:?}", input),
+            }
+        };
+
+        let fields = |value: Option<String>| -> crate::common::http_types::Result<Vec<HeaderName>> {
+            value
+                .iter()
+                .flat_map(|value| value.split(','))
+                .map(str::trim)
+                .filter(|field| !field.is_empty())
+                .map(str::parse::<HeaderName>)
+                .collect()
+        };
+
+        let directive = match name.as_str() {
+            "immutable" => Self::Immutable,
+            "max-age" => Self::MaxAge(seconds(value)?),
+            "max-stale" if value.is_none() => Self::MaxStale(None),
+            "max-stale" => Self::MaxStale(Some(seconds(value)?)),
+            "min-fresh" => Self::MinFresh(seconds(value)?),
+            "must-revalidate" => Self::MustRevalidate,
+            "no-cache" => Self::NoCache(fields(value)?),
+            "no-store" => Self::NoStore,
+            "no-transform" => Self::NoTransform,
+            "only-if-cached" => Self::OnlyIfCached,
+            "private" => Self::Private(fields(value)?),
+            "proxy-revalidate" => Self::ProxyRevalidate,
+            "public" => Self::Public,
+            "s-maxage" => Self::SMaxAge(seconds(value)?),
+            "stale-if-error" => Self::StaleIfError(seconds(value)?),
+            "stale-while-revalidate" => Self::StaleWhileRevalidate(seconds(value)?),
+            _ => Self::Extension(name, value),
+        };
+        Ok((directive, rest))
+    }
+}
+
+impl Display for CacheDirective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Immutable => f.write_str("immutable"),
+            Self::MaxAge(age) => write!(f, "max-age={}", age.as_secs()),
+            Self::MaxStale(None) => f.write_str("max-stale"),
+            Self::MaxStale(Some(age)) => write!(f, "max-stale={}", age.as_secs()),
+            Self::MinFresh(age) => write!(f, "min-fresh={}", age.as_secs()),
+            Self::MustRevalidate => f.write_str("must-revalidate"),
+            Self::NoCache(fields) => write_fields(f, "no-cache", fields),
+            Self::NoStore => f.write_str("no-store"),
+            Self::NoTransform => f.write_str("no-transform"),
+            Self::OnlyIfCached => f.write_str("only-if-cached"),
+            Self::Private(fields) => write_fields(f, "private", fields),
+            Self::ProxyRevalidate => f.write_str("proxy-revalidate"),
+            Self::Public => f.write_str("public"),
+            Self::SMaxAge(age) => write!(f, "s-maxage={}", age.as_secs()),
+            Self::StaleIfError(age) => write!(f, "stale-if-error={}", age.as_secs()),
+            Self::StaleWhileRevalidate(age) => {
+                write!(f, "stale-while-revalidate={}", age.as_secs())
+            }
+            Self::Extension(name, None) => f.write_str(name),
+            Self::Extension(name, Some(value))
+                if !value.is_empty() && parse_token(value).1.is_empty() =>
+            {
+                write!(f, "{}={}", name, value)
+            }
+            Self::Extension(name, Some(value)) => {
+                let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+                write!(f, "{}=\"{}\"", name, value)
+            }
+        }
+    }
+}
+
+fn write_fields(f: &mut fmt::Formatter<'_>, name: &str, fields: &[HeaderName]) -> fmt::Result {
+    if fields.is_empty() {
+        return f.write_str(name);
+    }
+    let fields = fields
+        .iter()
+        .map(HeaderName::as_str)
+        .collect::<Vec<_>>()
+        .join(", ");
+    write!(f, "{}=\"{}\"", name, fields)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheControl {
+    entries: Vec<CacheDirective>,
+}
+
+impl CacheControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(CACHE_CONTROL) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut cache_control = Self::new();
+        for value in headers.iter() {
+            let mut input = value.as_str();
+            loop {
+                input = input.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+                if input.is_empty() {
+                    break;
+                }
+                let (directive, rest) = CacheDirective::parse(input)?;
+                let rest = rest.trim_start();
+                crate::ensure_status!(
+                    rest.is_empty() || rest.starts_with(','),
+                    400,
+                    "Invalid cache directive {:?}",
+                    input
+                );
+                cache_control.push(directive)?;
+                input = rest;
+            }
+        }
+        Ok(Some(cache_control))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        CACHE_CONTROL
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = self
+            .entries
+            .iter()
+            .map(CacheDirective::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn push(&mut self, directive: CacheDirective) -> crate::common::http_types::Result<()> {
+        let valid = match &directive {
+            CacheDirective::NoCache(fields) | CacheDirective::Private(fields) => {
+                fields.iter().all(|field| is_token(field.as_str()))
+            }
+            CacheDirective::Extension(name, value) => {
+                is_token(name) && value.as_deref().map_or(true, is_field_text)
+            }
+            _ => true,
+        };
+        crate::ensure!(valid, "Invalid cache directive {:?}", directive);
+        self.entries.push(directive);
+        Ok(())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &CacheDirective> {
+        self.entries.iter()
+    }
+
+    pub fn contains(&self, directive: &CacheDirective) -> bool {
+        self.entries.contains(directive)
+    }
+
+    pub fn max_age(&self) -> Option<Duration> {
+        self.entries.iter().find_map(|directive| match directive {
+            CacheDirective::MaxAge(age) => Some(*age),
+            _ => None,
+        })
+    }
+}
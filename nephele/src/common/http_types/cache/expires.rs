@@ -0,0 +1,55 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, EXPIRES};
+use crate::proto::h1::date::{fmt_http_date, parse_http_date, HttpDate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Expires {
+    instant: SystemTime,
+}
+
+impl Expires {
+    pub fn new(dur: Duration) -> Self {
+        Self::new_at(SystemTime::now() + dur)
+    }
+
+    pub fn new_at(instant: SystemTime) -> Self {
+        Self {
+            instant: HttpDate::from(instant).into(),
+        }
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(EXPIRES) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let instant = parse_http_date(headers.last().as_str()).unwrap_or(UNIX_EPOCH);
+        Ok(Some(Self { instant }))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        EXPIRES
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = fmt_http_date(self.instant);
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn expiration(&self) -> SystemTime {
+        self.instant
+    }
+
+    pub fn set_expiration(&mut self, instant: SystemTime) {
+        self.instant = HttpDate::from(instant).into();
+    }
+}
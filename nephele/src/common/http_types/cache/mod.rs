@@ -0,0 +1,7 @@
+mod age;
+mod cache_control;
+mod expires;
+
+pub use age::Age;
+pub use cache_control::{CacheControl, CacheDirective};
+pub use expires::Expires;
@@ -0,0 +1,151 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, ETAG};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ETag {
+    Strong(String),
+    Weak(String),
+}
+
+impl ETag {
+    pub fn new(tag: String) -> crate::common::http_types::Result<Self> {
+        validate_tag(&tag)?;
+        Ok(Self::Strong(tag))
+    }
+
+    pub fn new_weak(tag: String) -> crate::common::http_types::Result<Self> {
+        validate_tag(&tag)?;
+        Ok(Self::Weak(tag))
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(ETAG) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        headers.last().as_str().parse().map(Some)
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        ETAG
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = self.to_string();
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn tag(&self) -> &str {
+        match self {
+            Self::Strong(tag) | Self::Weak(tag) => tag,
+        }
+    }
+
+    pub fn is_strong(&self) -> bool {
+        matches!(self, Self::Strong(_))
+    }
+
+    pub fn is_weak(&self) -> bool {
+        matches!(self, Self::Weak(_))
+    }
+
+    pub fn into_weak(self) -> Self {
+        match self {
+            Self::Strong(tag) | Self::Weak(tag) => Self::Weak(tag),
+        }
+    }
+
+    pub fn strong_eq(&self, other: &Self) -> bool {
+        self.is_strong() && other.is_strong() && self.tag() == other.tag()
+    }
+
+    pub fn weak_eq(&self, other: &Self) -> bool {
+        self.tag() == other.tag()
+    }
+
+    pub(crate) fn parse_list(input: &str) -> crate::common::http_types::Result<(Vec<Self>, bool)> {
+        let mut tags = Vec::new();
+        let mut wildcard = false;
+        let mut rest = input.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        while !rest.is_empty() {
+            if let Some(next) = rest.strip_prefix('*') {
+                wildcard = true;
+                rest = next;
+            } else {
+                let weak = rest.starts_with("W/");
+                let quoted = if weak { &rest[2..] } else { rest };
+                let end = match quoted.strip_prefix('"').and_then(|q| q.find('"')) {
+                    Some(end) => end + 2,
+                    None => crate::bail_status!(400, "Invalid entity tag list {:?}", input),
+                };
+                let tag = quoted[1..end - 1].to_string();
+                tags.push(if weak {
+                    Self::Weak(tag)
+                } else {
+                    Self::Strong(tag)
+                });
+                rest = &quoted[end..];
+            }
+
+            let trimmed = rest.trim_start();
+            crate::ensure_status!(
+                trimmed.is_empty() || trimmed.starts_with(','),
+                400,
+                "Invalid entity tag list {:?}",
+                input
+            );
+            rest = trimmed.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+        }
+        Ok((tags, wildcard))
+    }
+}
+
+fn validate_tag(tag: &str) -> crate::common::http_types::Result<()> {
+    crate::ensure!(
+        tag.bytes().all(|b| b == b'!' || (b'#'..=b'~').contains(&b)),
+        "Invalid entity tag {:?}",
+        tag
+    );
+    Ok(())
+}
+
+impl Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Strong(tag) => write!(f, "\"{}\"", tag),
+            Self::Weak(tag) => write!(f, "W/\"{}\"", tag),
+        }
+    }
+}
+
+impl FromStr for ETag {
+    type Err = crate::common::http_types::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (weak, quoted) = match s.strip_prefix("W/") {
+            Some(quoted) => (true, quoted),
+            None => (false, s),
+        };
+
+        let tag = match quoted.strip_prefix('"').and_then(|q| q.strip_suffix('"')) {
+            Some(tag) if !tag.contains('"') => tag.to_string(),
+            _ => crate::bail_status!(400, "Invalid entity tag {:?}", s),
+        };
+        Ok(if weak {
+            Self::Weak(tag)
+        } else {
+            Self::Strong(tag)
+        })
+    }
+}
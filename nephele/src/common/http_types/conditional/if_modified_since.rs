@@ -0,0 +1,59 @@
+use std::time::SystemTime;
+
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, IF_MODIFIED_SINCE};
+use crate::proto::h1::date::{fmt_http_date, parse_http_date, HttpDate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IfModifiedSince {
+    instant: SystemTime,
+}
+
+impl IfModifiedSince {
+    pub fn new(instant: SystemTime) -> Self {
+        Self {
+            instant: HttpDate::from(instant).into(),
+        }
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(IF_MODIFIED_SINCE) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let value = headers.last().as_str();
+        let instant = match parse_http_date(value) {
+            Ok(instant) => instant,
+            Err(_) => crate::bail_status!(400, "Invalid HTTP date {:?}", value),
+        };
+        Ok(Some(Self { instant }))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        IF_MODIFIED_SINCE
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = fmt_http_date(self.instant);
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn modified(&self) -> SystemTime {
+        self.instant
+    }
+
+    pub fn set_modified(&mut self, instant: SystemTime) {
+        self.instant = HttpDate::from(instant).into();
+    }
+
+    pub fn is_modified(&self, last_modified: SystemTime) -> bool {
+        SystemTime::from(HttpDate::from(last_modified)) > self.instant
+    }
+}
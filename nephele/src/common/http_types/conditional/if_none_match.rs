@@ -0,0 +1,73 @@
+use crate::common::http_types::conditional::ETag;
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, IF_NONE_MATCH};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IfNoneMatch {
+    entries: Vec<ETag>,
+    wildcard: bool,
+}
+
+impl IfNoneMatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(IF_NONE_MATCH) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut if_none_match = Self::new();
+        for value in headers.iter() {
+            let (entries, wildcard) = ETag::parse_list(value.as_str())?;
+            if_none_match.entries.extend(entries);
+            if_none_match.wildcard |= wildcard;
+        }
+        Ok(Some(if_none_match))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        IF_NONE_MATCH
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = match self.wildcard {
+            true => "*".to_string(),
+            false => self
+                .entries
+                .iter()
+                .map(ETag::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn push(&mut self, etag: ETag) {
+        self.entries.push(etag);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ETag> {
+        self.entries.iter()
+    }
+
+    pub fn wildcard(&self) -> bool {
+        self.wildcard
+    }
+
+    pub fn set_wildcard(&mut self, wildcard: bool) {
+        self.wildcard = wildcard;
+    }
+
+    pub fn matches(&self, etag: &ETag) -> bool {
+        self.wildcard || self.entries.iter().any(|entry| entry.weak_eq(etag))
+    }
+}
@@ -0,0 +1,15 @@
+mod etag;
+mod if_match;
+mod if_modified_since;
+mod if_none_match;
+mod if_unmodified_since;
+mod last_modified;
+mod vary;
+
+pub use etag::ETag;
+pub use if_match::IfMatch;
+pub use if_modified_since::IfModifiedSince;
+pub use if_none_match::IfNoneMatch;
+pub use if_unmodified_since::IfUnmodifiedSince;
+pub use last_modified::LastModified;
+pub use vary::Vary;
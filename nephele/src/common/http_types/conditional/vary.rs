@@ -0,0 +1,80 @@
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, VARY};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vary {
+    entries: Vec<HeaderName>,
+    wildcard: bool,
+}
+
+impl Vary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(VARY) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut vary = Self::new();
+        for value in headers.iter() {
+            for name in value.as_str().split(',').map(str::trim) {
+                match name {
+                    "" => {}
+                    "*" => vary.wildcard = true,
+                    name => vary.push(name.parse::<HeaderName>()?),
+                }
+            }
+        }
+        Ok(Some(vary))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        VARY
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = match self.wildcard {
+            true => "*".to_string(),
+            false => self
+                .entries
+                .iter()
+                .map(HeaderName::as_str)
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn push(&mut self, name: impl Into<HeaderName>) {
+        let name = name.into();
+        if !self.entries.contains(&name) {
+            self.entries.push(name);
+        }
+    }
+
+    pub fn contains(&self, name: impl Into<HeaderName>) -> bool {
+        let name = name.into();
+        self.wildcard || self.entries.contains(&name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &HeaderName> {
+        self.entries.iter()
+    }
+
+    pub fn wildcard(&self) -> bool {
+        self.wildcard
+    }
+
+    pub fn set_wildcard(&mut self, wildcard: bool) {
+        self.wildcard = wildcard;
+    }
+}
@@ -0,0 +1,124 @@
+use std::str::FromStr;
+
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, ACCEPT};
+use crate::common::http_types::Mime;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Accept {
+    entries: Vec<(Mime, f32)>,
+}
+
+impl Accept {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(ACCEPT) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut accept = Self::new();
+        for value in headers.iter() {
+            for element in value.as_str().split(',').map(str::trim) {
+                if !element.is_empty() {
+                    let (media_range, quality) = parse_element(element)?;
+                    accept.push(media_range, quality);
+                }
+            }
+        }
+        Ok(Some(accept))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        ACCEPT
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = self
+            .entries
+            .iter()
+            .map(|(mime, quality)| match *quality >= 1.0 {
+                true => mime.to_string(),
+                false => {
+                    let quality = format!("{:.3}", quality);
+                    let quality = quality.trim_end_matches('0').trim_end_matches('.');
+                    format!("{};q={}", mime, quality)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn push(&mut self, media_range: impl Into<Mime>, quality: f32) {
+        self.entries.push((media_range.into(), quality));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Mime, f32)> {
+        self.entries.iter().map(|(mime, quality)| (mime, *quality))
+    }
+
+    pub fn quality(&self, mime: &Mime) -> f32 {
+        self.entries
+            .iter()
+            .filter_map(|(range, quality)| specificity(range, mime).map(|s| (s, *quality)))
+            .max_by_key(|(specificity, _)| *specificity)
+            .map_or(0.0, |(_, quality)| quality)
+    }
+
+    pub fn negotiate(&self, available: &[Mime]) -> Option<Mime> {
+        let mut best: Option<(f32, &Mime)> = None;
+        for mime in available {
+            let quality = self.quality(mime);
+            if quality > 0.0 && best.map_or(true, |(best, _)| quality > best) {
+                best = Some((quality, mime));
+            }
+        }
+        best.map(|(_, mime)| mime.clone())
+    }
+}
+
+fn parse_element(element: &str) -> crate::common::http_types::Result<(Mime, f32)> {
+    let mut params = element.split(';').map(str::trim);
+    let mut media_range = params.next().unwrap_or_default().to_string();
+    let mut quality = 1.0;
+
+    for param in params.filter(|param| !param.is_empty()) {
+        match param.split_once('=') {
+            Some((name, value)) if name.trim().eq_ignore_ascii_case("q") => {
+                quality = match value.trim().parse::<f32>() {
+                    Ok(q) if (0.0..=1.0).contains(&q) => q,
+                    _ => crate::bail_status!(400, "Invalid quality value {:?}", value),
+                };
+                break;
+            }
+            _ => {
+                media_range.push(';');
+                media_range.push_str(param);
+            }
+        }
+    }
+
+    match Mime::from_str(&media_range) {
+        Ok(mime) => Ok((mime, quality)),
+        Err(_) => crate::bail_status!(400, "Invalid media range {:?}", element),
+    }
+}
+
+fn specificity(range: &Mime, mime: &Mime) -> Option<u8> {
+    match (range.basetype(), range.subtype()) {
+        ("*", "*") => Some(0),
+        (basetype, "*") if basetype.eq_ignore_ascii_case(mime.basetype()) => Some(1),
+        _ if range.essence().eq_ignore_ascii_case(mime.essence()) => Some(2),
+        _ => None,
+    }
+}
@@ -0,0 +1,138 @@
+use crate::common::http_types::content::Encoding;
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, ACCEPT_ENCODING};
+use crate::common::http_types::parse_utils::parse_token;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AcceptEncoding {
+    entries: Vec<(Encoding, f32)>,
+    wildcard: Option<f32>,
+}
+
+impl AcceptEncoding {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(ACCEPT_ENCODING) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut accept = Self::new();
+        for value in headers.iter() {
+            for element in value.as_str().split(',') {
+                let element = element.trim();
+                if element.is_empty() {
+                    continue;
+                }
+
+                let (coding, rest) = match parse_token(element) {
+                    (Some(coding), rest) => (coding, rest),
+                    (None, _) => crate::bail_status!(400, "Invalid Accept-Encoding {:?}", element),
+                };
+                let quality = parse_quality(rest)?;
+
+                if coding == "*" {
+                    accept.wildcard = Some(quality);
+                } else if let Ok(encoding) = coding.parse() {
+                    accept.push(encoding, quality);
+                }
+            }
+        }
+        Ok(Some(accept))
+    }
+
+    pub fn push(&mut self, encoding: Encoding, quality: f32) {
+        self.entries.push((encoding, quality));
+    }
+
+    pub fn set_wildcard(&mut self, quality: Option<f32>) {
+        self.wildcard = quality;
+    }
+
+    pub fn quality(&self, encoding: Encoding) -> f32 {
+        let explicit = self
+            .entries
+            .iter()
+            .find(|(candidate, _)| *candidate == encoding)
+            .map(|(_, quality)| *quality);
+
+        match (explicit, self.wildcard) {
+            (Some(quality), _) | (None, Some(quality)) => quality,
+            (None, None) if encoding == Encoding::Identity => 1.0,
+            (None, None) => 0.0,
+        }
+    }
+
+    pub fn negotiate(&self, available: &[Encoding]) -> Option<Encoding> {
+        let implicit_identity = self.wildcard.is_none()
+            && !self
+                .entries
+                .iter()
+                .any(|(encoding, _)| *encoding == Encoding::Identity);
+
+        let mut best: Option<(f32, Encoding)> = None;
+        for &encoding in available {
+            let quality = match encoding {
+                Encoding::Identity if implicit_identity => f32::MIN_POSITIVE,
+                encoding => self.quality(encoding),
+            };
+            if quality > 0.0 && best.map_or(true, |(best, _)| quality > best) {
+                best = Some((quality, encoding));
+            }
+        }
+        best.map(|(_, encoding)| encoding)
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        ACCEPT_ENCODING
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let mut parts = self
+            .entries
+            .iter()
+            .map(|(encoding, quality)| format_entry(encoding.as_str(), *quality))
+            .collect::<Vec<_>>();
+        if let Some(quality) = self.wildcard {
+            parts.push(format_entry("*", quality));
+        }
+        let output = parts.join(", ");
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+}
+
+fn parse_quality(params: &str) -> crate::common::http_types::Result<f32> {
+    let mut quality = 1.0;
+    for param in params.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+        let (name, value) = match param.split_once('=') {
+            Some((name, value)) => (name.trim(), value.trim()),
+            None => crate::bail_status!(400, "Invalid Accept-Encoding parameter {:?}", param),
+        };
+        if name.eq_ignore_ascii_case("q") {
+            quality = match value.parse::<f32>() {
+                Ok(q) if (0.0..=1.0).contains(&q) => q,
+                _ => crate::bail_status!(400, "Invalid quality value {:?}", value),
+            };
+        }
+    }
+    Ok(quality)
+}
+
+fn format_entry(coding: &str, quality: f32) -> String {
+    if quality >= 1.0 {
+        coding.to_string()
+    } else {
+        let quality = format!("{:.3}", quality);
+        let quality = quality.trim_end_matches('0').trim_end_matches('.');
+        format!("{};q={}", coding, quality)
+    }
+}
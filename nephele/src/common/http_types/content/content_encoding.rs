@@ -0,0 +1,62 @@
+use crate::common::http_types::content::Encoding;
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, CONTENT_ENCODING};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentEncoding {
+    encoding: Encoding,
+}
+
+impl ContentEncoding {
+    pub fn new(encoding: Encoding) -> Self {
+        Self { encoding }
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(CONTENT_ENCODING) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut encoding = Encoding::Identity;
+        for value in headers.iter() {
+            for coding in value.as_str().split(',').filter(|c| !c.trim().is_empty()) {
+                match coding.parse()? {
+                    Encoding::Identity => {}
+                    parsed => {
+                        crate::ensure_status!(
+                            encoding == Encoding::Identity,
+                            415,
+                            "Multiple content codings are not supported"
+                        );
+                        encoding = parsed;
+                    }
+                }
+            }
+        }
+        Ok(Some(Self { encoding }))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        CONTENT_ENCODING
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = self.encoding.as_str().to_string();
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    pub fn set_encoding(&mut self, encoding: Encoding) {
+        self.encoding = encoding;
+    }
+}
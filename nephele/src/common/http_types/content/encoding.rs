@@ -0,0 +1,46 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Gzip,
+    Deflate,
+    Brotli,
+    Identity,
+}
+
+impl Encoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+            Encoding::Brotli => "br",
+            Encoding::Identity => "identity",
+        }
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = crate::common::http_types::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("gzip") || s.eq_ignore_ascii_case("x-gzip") {
+            Ok(Encoding::Gzip)
+        } else if s.eq_ignore_ascii_case("deflate") {
+            Ok(Encoding::Deflate)
+        } else if s.eq_ignore_ascii_case("br") {
+            Ok(Encoding::Brotli)
+        } else if s.eq_ignore_ascii_case("identity") {
+            Ok(Encoding::Identity)
+        } else {
+            crate::bail_status!(415, "Unsupported content coding {:?}", s)
+        }
+    }
+}
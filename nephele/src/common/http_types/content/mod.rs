@@ -1,3 +1,11 @@
+mod accept;
+mod accept_encoding;
+mod content_encoding;
 mod content_length;
+mod encoding;
 
+pub use accept::Accept;
+pub use accept_encoding::AcceptEncoding;
+pub use content_encoding::ContentEncoding;
 pub use content_length::ContentLength;
+pub use encoding::Encoding;
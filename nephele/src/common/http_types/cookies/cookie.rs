@@ -0,0 +1,238 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use crate::proto::h1::date::{fmt_http_date, parse_http_date};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl SameSite {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Strict => "Strict",
+            Self::Lax => "Lax",
+            Self::None => "None",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    name: String,
+    value: String,
+    expires: Option<SystemTime>,
+    max_age: Option<Duration>,
+    domain: Option<String>,
+    path: Option<String>,
+    secure: bool,
+    http_only: bool,
+    same_site: Option<SameSite>,
+}
+
+impl Cookie {
+    pub fn new(
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> crate::common::http_types::Result<Self> {
+        let (name, value) = (name.into(), value.into());
+        crate::ensure!(
+            !name.is_empty() && is_cookie_text(&name, &['=', ' ', '\t']),
+            "Invalid cookie name {:?}",
+            name
+        );
+        crate::ensure!(
+            is_cookie_text(&value, &[]),
+            "Invalid cookie value {:?}",
+            value
+        );
+        Ok(Self::from_pair(name, value))
+    }
+
+    fn from_pair(name: String, value: String) -> Self {
+        Self {
+            name,
+            value,
+            expires: None,
+            max_age: None,
+            domain: None,
+            path: None,
+            secure: false,
+            http_only: false,
+            same_site: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn set_value(&mut self, value: impl Into<String>) -> crate::common::http_types::Result<()> {
+        let value = value.into();
+        crate::ensure!(
+            is_cookie_text(&value, &[]),
+            "Invalid cookie value {:?}",
+            value
+        );
+        self.value = value;
+        Ok(())
+    }
+
+    pub fn expires(&self) -> Option<SystemTime> {
+        self.expires
+    }
+
+    pub fn set_expires(&mut self, expires: Option<SystemTime>) {
+        self.expires = expires;
+    }
+
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    pub fn set_max_age(&mut self, max_age: Option<Duration>) {
+        self.max_age = max_age;
+    }
+
+    pub fn domain(&self) -> Option<&str> {
+        self.domain.as_deref()
+    }
+
+    pub fn set_domain(&mut self, domain: Option<String>) -> crate::common::http_types::Result<()> {
+        if let Some(domain) = &domain {
+            crate::ensure!(
+                is_cookie_text(domain, &[]),
+                "Invalid cookie domain {:?}",
+                domain
+            );
+        }
+        self.domain = domain;
+        Ok(())
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    pub fn set_path(&mut self, path: Option<String>) -> crate::common::http_types::Result<()> {
+        if let Some(path) = &path {
+            crate::ensure!(is_cookie_text(path, &[]), "Invalid cookie path {:?}", path);
+        }
+        self.path = path;
+        Ok(())
+    }
+
+    pub fn secure(&self) -> bool {
+        self.secure
+    }
+
+    pub fn set_secure(&mut self, secure: bool) {
+        self.secure = secure;
+    }
+
+    pub fn http_only(&self) -> bool {
+        self.http_only
+    }
+
+    pub fn set_http_only(&mut self, http_only: bool) {
+        self.http_only = http_only;
+    }
+
+    pub fn same_site(&self) -> Option<SameSite> {
+        self.same_site
+    }
+
+    pub fn set_same_site(&mut self, same_site: Option<SameSite>) {
+        self.same_site = same_site;
+    }
+
+    pub(crate) fn parse_pair(pair: &str) -> crate::common::http_types::Result<Self> {
+        let (name, value) = match pair.split_once('=') {
+            Some((name, value)) if !name.trim().is_empty() => (name.trim(), value.trim()),
+            _ => crate::bail_status!(400, "Invalid cookie {:?}", pair),
+        };
+        let value = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+            Some(unquoted) => unquoted,
+            None => value,
+        };
+        Ok(Self::from_pair(name.to_string(), value.to_string()))
+    }
+}
+
+fn is_cookie_text(input: &str, forbidden: &[char]) -> bool {
+    input
+        .chars()
+        .all(|c| c.is_ascii() && !c.is_ascii_control() && c != ';' && !forbidden.contains(&c))
+}
+
+impl Display for Cookie {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.value)?;
+        if let Some(expires) = self.expires {
+            write!(f, "; Expires={}", fmt_http_date(expires))?;
+        }
+        if let Some(max_age) = self.max_age {
+            write!(f, "; Max-Age={}", max_age.as_secs())?;
+        }
+        if let Some(domain) = &self.domain {
+            write!(f, "; Domain={}", domain)?;
+        }
+        if let Some(path) = &self.path {
+            write!(f, "; Path={}", path)?;
+        }
+        if self.secure {
+            f.write_str("; Secure")?;
+        }
+        if self.http_only {
+            f.write_str("; HttpOnly")?;
+        }
+        if let Some(same_site) = self.same_site {
+            write!(f, "; SameSite={}", same_site.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Cookie {
+    type Err = crate::common::http_types::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut attributes = s.split(';');
+        let mut cookie = Self::parse_pair(attributes.next().unwrap_or_default())?;
+
+        for attribute in attributes.map(str::trim).filter(|a| !a.is_empty()) {
+            let (name, value) = match attribute.split_once('=') {
+                Some((name, value)) => (name.trim(), value.trim()),
+                None => (attribute, ""),
+            };
+            match name.to_ascii_lowercase().as_str() {
+                "expires" => cookie.expires = parse_http_date(value).ok(),
+                "max-age" => cookie.max_age = value.parse().ok().map(Duration::from_secs),
+                "domain" if !value.is_empty() => {
+                    cookie.domain = Some(value.trim_start_matches('.').to_string())
+                }
+                "path" if value.starts_with('/') => cookie.path = Some(value.to_string()),
+                "secure" => cookie.secure = true,
+                "httponly" => cookie.http_only = true,
+                "samesite" => {
+                    cookie.same_site = match value.to_ascii_lowercase().as_str() {
+                        "strict" => Some(SameSite::Strict),
+                        "lax" => Some(SameSite::Lax),
+                        "none" => Some(SameSite::None),
+                        _ => None,
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(cookie)
+    }
+}
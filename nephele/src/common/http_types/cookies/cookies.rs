@@ -0,0 +1,66 @@
+use crate::common::http_types::cookies::Cookie;
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, COOKIE};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cookies {
+    entries: Vec<Cookie>,
+}
+
+impl Cookies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(COOKIE) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut cookies = Self::new();
+        for value in headers.iter() {
+            for pair in value.as_str().split(';').map(str::trim) {
+                if !pair.is_empty() {
+                    cookies.push(Cookie::parse_pair(pair)?);
+                }
+            }
+        }
+        Ok(Some(cookies))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        COOKIE
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = self
+            .entries
+            .iter()
+            .map(|cookie| format!("{}={}", cookie.name(), cookie.value()))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn push(&mut self, cookie: Cookie) {
+        self.entries.push(cookie);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|cookie| cookie.name() == name)
+            .map(Cookie::value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Cookie> {
+        self.entries.iter()
+    }
+}
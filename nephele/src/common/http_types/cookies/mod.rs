@@ -0,0 +1,7 @@
+mod cookie;
+mod cookies;
+mod set_cookie;
+
+pub use cookie::{Cookie, SameSite};
+pub use cookies::Cookies;
+pub use set_cookie::SetCookie;
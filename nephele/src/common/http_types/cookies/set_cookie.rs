@@ -0,0 +1,68 @@
+use crate::common::http_types::cookies::Cookie;
+use crate::common::http_types::headers::{
+    HeaderName, HeaderValue, HeaderValues, Headers, SET_COOKIE,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SetCookie {
+    entries: Vec<Cookie>,
+}
+
+impl SetCookie {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(SET_COOKIE) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut set_cookie = Self::new();
+        for value in headers.iter() {
+            set_cookie.push(value.as_str().parse()?);
+        }
+        Ok(Some(set_cookie))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), &self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        SET_COOKIE
+    }
+
+    pub fn value(&self) -> HeaderValues {
+        self.entries
+            .iter()
+            .map(|cookie| {
+                let output = cookie.to_string();
+                unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+            })
+            .collect()
+    }
+
+    pub fn push(&mut self, cookie: Cookie) {
+        self.entries.retain(|entry| {
+            entry.name() != cookie.name()
+                || entry.domain() != cookie.domain()
+                || entry.path() != cookie.path()
+        });
+        self.entries.push(cookie);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Cookie> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|cookie| cookie.name() == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Cookie> {
+        self.entries.iter()
+    }
+}
@@ -1,10 +1,108 @@
 use super::HeaderName;
 
+pub const ACCEPT: HeaderName = HeaderName::from_lowercase_str("accept");
+pub const ACCEPT_CHARSET: HeaderName = HeaderName::from_lowercase_str("accept-charset");
+pub const ACCEPT_ENCODING: HeaderName = HeaderName::from_lowercase_str("accept-encoding");
+pub const ACCEPT_LANGUAGE: HeaderName = HeaderName::from_lowercase_str("accept-language");
+pub const ACCEPT_RANGES: HeaderName = HeaderName::from_lowercase_str("accept-ranges");
+pub const ACCESS_CONTROL_ALLOW_CREDENTIALS: HeaderName =
+    HeaderName::from_lowercase_str("access-control-allow-credentials");
+pub const ACCESS_CONTROL_ALLOW_HEADERS: HeaderName =
+    HeaderName::from_lowercase_str("access-control-allow-headers");
+pub const ACCESS_CONTROL_ALLOW_METHODS: HeaderName =
+    HeaderName::from_lowercase_str("access-control-allow-methods");
+pub const ACCESS_CONTROL_ALLOW_ORIGIN: HeaderName =
+    HeaderName::from_lowercase_str("access-control-allow-origin");
+pub const ACCESS_CONTROL_EXPOSE_HEADERS: HeaderName =
+    HeaderName::from_lowercase_str("access-control-expose-headers");
+pub const ACCESS_CONTROL_MAX_AGE: HeaderName =
+    HeaderName::from_lowercase_str("access-control-max-age");
+pub const ACCESS_CONTROL_REQUEST_HEADERS: HeaderName =
+    HeaderName::from_lowercase_str("access-control-request-headers");
+pub const ACCESS_CONTROL_REQUEST_METHOD: HeaderName =
+    HeaderName::from_lowercase_str("access-control-request-method");
+pub const AGE: HeaderName = HeaderName::from_lowercase_str("age");
+pub const ALLOW: HeaderName = HeaderName::from_lowercase_str("allow");
+pub const ALT_SVC: HeaderName = HeaderName::from_lowercase_str("alt-svc");
+pub const AUTHORIZATION: HeaderName = HeaderName::from_lowercase_str("authorization");
+pub const CACHE_CONTROL: HeaderName = HeaderName::from_lowercase_str("cache-control");
+pub const CLEAR_SITE_DATA: HeaderName = HeaderName::from_lowercase_str("clear-site-data");
 pub const CONNECTION: HeaderName = HeaderName::from_lowercase_str("connection");
-pub const UPGRADE: HeaderName = HeaderName::from_lowercase_str("upgrade");
-pub const CONTENT_TYPE: HeaderName = HeaderName::from_lowercase_str("content-type");
-pub const HOST: HeaderName = HeaderName::from_lowercase_str("host");
+pub const CONTENT_DISPOSITION: HeaderName = HeaderName::from_lowercase_str("content-disposition");
+pub const CONTENT_ENCODING: HeaderName = HeaderName::from_lowercase_str("content-encoding");
+pub const CONTENT_LANGUAGE: HeaderName = HeaderName::from_lowercase_str("content-language");
 pub const CONTENT_LENGTH: HeaderName = HeaderName::from_lowercase_str("content-length");
+pub const CONTENT_LOCATION: HeaderName = HeaderName::from_lowercase_str("content-location");
+pub const CONTENT_MD5: HeaderName = HeaderName::from_lowercase_str("content-md5");
+pub const CONTENT_RANGE: HeaderName = HeaderName::from_lowercase_str("content-range");
+pub const CONTENT_SECURITY_POLICY: HeaderName =
+    HeaderName::from_lowercase_str("content-security-policy");
+pub const CONTENT_SECURITY_POLICY_REPORT_ONLY: HeaderName =
+    HeaderName::from_lowercase_str("content-security-policy-report-only");
+pub const CONTENT_TYPE: HeaderName = HeaderName::from_lowercase_str("content-type");
+pub const COOKIE: HeaderName = HeaderName::from_lowercase_str("cookie");
 pub const DATE: HeaderName = HeaderName::from_lowercase_str("date");
-pub const TRANSFER_ENCODING: HeaderName = HeaderName::from_lowercase_str("transfer-encoding");
+pub const DNT: HeaderName = HeaderName::from_lowercase_str("dnt");
+pub const ETAG: HeaderName = HeaderName::from_lowercase_str("etag");
 pub const EXPECT: HeaderName = HeaderName::from_lowercase_str("expect");
+pub const EXPIRES: HeaderName = HeaderName::from_lowercase_str("expires");
+pub const FORWARDED: HeaderName = HeaderName::from_lowercase_str("forwarded");
+pub const FROM: HeaderName = HeaderName::from_lowercase_str("from");
+pub const HOST: HeaderName = HeaderName::from_lowercase_str("host");
+pub const HTTP2_SETTINGS: HeaderName = HeaderName::from_lowercase_str("http2-settings");
+pub const IF_MATCH: HeaderName = HeaderName::from_lowercase_str("if-match");
+pub const IF_MODIFIED_SINCE: HeaderName = HeaderName::from_lowercase_str("if-modified-since");
+pub const IF_NONE_MATCH: HeaderName = HeaderName::from_lowercase_str("if-none-match");
+pub const IF_RANGE: HeaderName = HeaderName::from_lowercase_str("if-range");
+pub const IF_UNMODIFIED_SINCE: HeaderName = HeaderName::from_lowercase_str("if-unmodified-since");
+pub const KEEP_ALIVE: HeaderName = HeaderName::from_lowercase_str("keep-alive");
+pub const LAST_MODIFIED: HeaderName = HeaderName::from_lowercase_str("last-modified");
+pub const LINK: HeaderName = HeaderName::from_lowercase_str("link");
+pub const LOCATION: HeaderName = HeaderName::from_lowercase_str("location");
+pub const MAX_FORWARDS: HeaderName = HeaderName::from_lowercase_str("max-forwards");
+pub const ORIGIN: HeaderName = HeaderName::from_lowercase_str("origin");
+pub const PRAGMA: HeaderName = HeaderName::from_lowercase_str("pragma");
+pub const PRIORITY: HeaderName = HeaderName::from_lowercase_str("priority");
+pub const PROXY_AUTHENTICATE: HeaderName = HeaderName::from_lowercase_str("proxy-authenticate");
+pub const PROXY_AUTHORIZATION: HeaderName = HeaderName::from_lowercase_str("proxy-authorization");
+pub const PROXY_CONNECTION: HeaderName = HeaderName::from_lowercase_str("proxy-connection");
+pub const RANGE: HeaderName = HeaderName::from_lowercase_str("range");
+pub const REFERER: HeaderName = HeaderName::from_lowercase_str("referer");
+pub const REFERRER_POLICY: HeaderName = HeaderName::from_lowercase_str("referrer-policy");
+pub const RETRY_AFTER: HeaderName = HeaderName::from_lowercase_str("retry-after");
+pub const SEC_WEBSOCKET_ACCEPT: HeaderName = HeaderName::from_lowercase_str("sec-websocket-accept");
+pub const SEC_WEBSOCKET_EXTENSIONS: HeaderName =
+    HeaderName::from_lowercase_str("sec-websocket-extensions");
+pub const SEC_WEBSOCKET_KEY: HeaderName = HeaderName::from_lowercase_str("sec-websocket-key");
+pub const SEC_WEBSOCKET_PROTOCOL: HeaderName =
+    HeaderName::from_lowercase_str("sec-websocket-protocol");
+pub const SEC_WEBSOCKET_VERSION: HeaderName =
+    HeaderName::from_lowercase_str("sec-websocket-version");
+pub const SERVER: HeaderName = HeaderName::from_lowercase_str("server");
+pub const SERVER_TIMING: HeaderName = HeaderName::from_lowercase_str("server-timing");
+pub const SET_COOKIE: HeaderName = HeaderName::from_lowercase_str("set-cookie");
+pub const SOURCE_MAP: HeaderName = HeaderName::from_lowercase_str("source-map");
+pub const STRICT_TRANSPORT_SECURITY: HeaderName =
+    HeaderName::from_lowercase_str("strict-transport-security");
+pub const TE: HeaderName = HeaderName::from_lowercase_str("te");
+pub const TIMING_ALLOW_ORIGIN: HeaderName = HeaderName::from_lowercase_str("timing-allow-origin");
+pub const TRAILER: HeaderName = HeaderName::from_lowercase_str("trailer");
+pub const TRANSFER_ENCODING: HeaderName = HeaderName::from_lowercase_str("transfer-encoding");
+pub const UPGRADE: HeaderName = HeaderName::from_lowercase_str("upgrade");
+pub const UPGRADE_INSECURE_REQUESTS: HeaderName =
+    HeaderName::from_lowercase_str("upgrade-insecure-requests");
+pub const USER_AGENT: HeaderName = HeaderName::from_lowercase_str("user-agent");
+pub const VARY: HeaderName = HeaderName::from_lowercase_str("vary");
+pub const VIA: HeaderName = HeaderName::from_lowercase_str("via");
+pub const WARNING: HeaderName = HeaderName::from_lowercase_str("warning");
+pub const WWW_AUTHENTICATE: HeaderName = HeaderName::from_lowercase_str("www-authenticate");
+pub const X_CONTENT_TYPE_OPTIONS: HeaderName =
+    HeaderName::from_lowercase_str("x-content-type-options");
+pub const X_DNS_PREFETCH_CONTROL: HeaderName =
+    HeaderName::from_lowercase_str("x-dns-prefetch-control");
+pub const X_FORWARDED_FOR: HeaderName = HeaderName::from_lowercase_str("x-forwarded-for");
+pub const X_FORWARDED_HOST: HeaderName = HeaderName::from_lowercase_str("x-forwarded-host");
+pub const X_FORWARDED_PROTO: HeaderName = HeaderName::from_lowercase_str("x-forwarded-proto");
+pub const X_FRAME_OPTIONS: HeaderName = HeaderName::from_lowercase_str("x-frame-options");
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_lowercase_str("x-request-id");
+pub const X_XSS_PROTECTION: HeaderName = HeaderName::from_lowercase_str("x-xss-protection");
@@ -2,11 +2,10 @@ use std::convert::TryFrom;
 use std::fmt::{self, Debug, Display};
 use std::str::FromStr;
 
+use crate::common::http_types::cookies::Cookie;
 use crate::common::http_types::headers::HeaderValues;
 use crate::common::http_types::Error;
 use crate::common::http_types::Mime;
-#[cfg(feature = "cookies")]
-use crate::Cookie;
 
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct HeaderValue {
@@ -39,9 +38,8 @@ impl From<Mime> for HeaderValue {
     }
 }
 
-#[cfg(feature = "cookies")]
-impl From<Cookie<'_>> for HeaderValue {
-    fn from(cookie: Cookie<'_>) -> Self {
+impl From<Cookie> for HeaderValue {
+    fn from(cookie: Cookie) -> Self {
         HeaderValue {
             inner: cookie.to_string(),
         }
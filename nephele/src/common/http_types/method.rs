@@ -23,6 +23,10 @@ impl Method {
             Method::Get | Method::Head | Method::Options | Method::Trace
         )
     }
+
+    pub fn is_idempotent(&self) -> bool {
+        self.is_safe() || matches!(self, Method::Put | Method::Delete)
+    }
 }
 
 struct MethodVisitor;
@@ -34,12 +34,17 @@ impl Mime {
 
     pub fn from_extension(extension: impl AsRef<str>) -> Option<Self> {
         match extension.as_ref() {
-            "html" => Some(HTML),
+            "html" | "htm" => Some(HTML),
             "js" | "mjs" | "jsonp" => Some(JAVASCRIPT),
             "json" => Some(JSON),
             "css" => Some(CSS),
             "svg" => Some(SVG),
             "xml" => Some(XML),
+            "txt" => Some(PLAIN),
+            "png" => Some(PNG),
+            "jpg" | "jpeg" => Some(JPEG),
+            "ico" => Some(ICO),
+            "wasm" => Some(WASM),
             _ => None,
         }
     }
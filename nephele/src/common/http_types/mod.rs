@@ -19,7 +19,7 @@ pub use crate::common::http_types::url::Url;
 
 mod error;
 
-mod body;
+pub(crate) mod body;
 mod extensions;
 mod macros;
 mod method;
@@ -30,9 +30,15 @@ mod status;
 mod status_code;
 mod version;
 
+pub mod auth;
+pub mod cache;
+pub mod conditional;
 pub mod content;
+pub mod cookies;
 pub mod headers;
 pub mod mime;
+pub mod other;
+pub mod proxies;
 pub mod upgrade;
 
 pub use body::Body;
@@ -0,0 +1,65 @@
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, ALLOW};
+use crate::common::http_types::Method;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Allow {
+    entries: Vec<Method>,
+}
+
+impl Allow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(ALLOW) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let mut allow = Self::new();
+        for value in headers.iter() {
+            for method in value.as_str().split(',').map(str::trim) {
+                if let Ok(method) = method.parse() {
+                    allow.push(method);
+                }
+            }
+        }
+        Ok(Some(allow))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        ALLOW
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = self
+            .entries
+            .iter()
+            .map(Method::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn push(&mut self, method: Method) {
+        if !self.entries.contains(&method) {
+            self.entries.push(method);
+        }
+    }
+
+    pub fn contains(&self, method: Method) -> bool {
+        self.entries.contains(&method)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Method> {
+        self.entries.iter()
+    }
+}
@@ -0,0 +1,59 @@
+use std::time::SystemTime;
+
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, DATE};
+use crate::proto::h1::date::{fmt_http_date, parse_http_date, HttpDate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    at: SystemTime,
+}
+
+impl Date {
+    pub fn new(at: SystemTime) -> Self {
+        Self {
+            at: HttpDate::from(at).into(),
+        }
+    }
+
+    pub fn now() -> Self {
+        Self::new(SystemTime::now())
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(DATE) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let value = headers.last().as_str();
+        let at = match parse_http_date(value) {
+            Ok(at) => at,
+            Err(_) => crate::bail_status!(400, "Invalid HTTP date {:?}", value),
+        };
+        Ok(Some(Self { at }))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        DATE
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = fmt_http_date(self.at);
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn date(&self) -> SystemTime {
+        self.at
+    }
+
+    pub fn set_date(&mut self, at: SystemTime) {
+        self.at = HttpDate::from(at).into();
+    }
+}
@@ -0,0 +1,44 @@
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, EXPECT};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Expect {
+    _priv: (),
+}
+
+impl Expect {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(EXPECT) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let value = headers.last().as_str().trim();
+        crate::ensure_status!(
+            value.eq_ignore_ascii_case("100-continue"),
+            417,
+            "Unsupported expectation {:?}",
+            value
+        );
+        Ok(Some(Self::new()))
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        EXPECT
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = "100-continue".to_string();
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+}
@@ -0,0 +1,9 @@
+mod allow;
+mod date;
+mod expect;
+mod retry_after;
+
+pub use allow::Allow;
+pub use date::Date;
+pub use expect::Expect;
+pub use retry_after::RetryAfter;
@@ -0,0 +1,62 @@
+use std::time::{Duration, SystemTime};
+
+use crate::common::http_types::headers::{HeaderName, HeaderValue, Headers, RETRY_AFTER};
+use crate::proto::h1::date::{fmt_http_date, parse_http_date, HttpDate};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryAfter {
+    Delay(Duration),
+    At(SystemTime),
+}
+
+impl RetryAfter {
+    pub fn new(dur: Duration) -> Self {
+        Self::Delay(dur)
+    }
+
+    pub fn new_at(at: SystemTime) -> Self {
+        Self::At(HttpDate::from(at).into())
+    }
+
+    pub fn from_headers(
+        headers: impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = match headers.as_ref().get(RETRY_AFTER) {
+            Some(headers) => headers,
+            None => return Ok(None),
+        };
+
+        let value = headers.last().as_str().trim();
+        if let Ok(secs) = value.parse::<u64>() {
+            return Ok(Some(Self::Delay(Duration::from_secs(secs))));
+        }
+        match parse_http_date(value) {
+            Ok(at) => Ok(Some(Self::At(at))),
+            Err(_) => crate::bail_status!(400, "Invalid Retry-After {:?}", value),
+        }
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        RETRY_AFTER
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let output = match self {
+            Self::Delay(dur) => dur.as_secs().to_string(),
+            Self::At(at) => fmt_http_date(*at),
+        };
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+
+    pub fn delay_from(&self, now: SystemTime) -> Duration {
+        match self {
+            Self::Delay(dur) => *dur,
+            Self::At(at) => at.duration_since(now).unwrap_or_default(),
+        }
+    }
+}
@@ -1,23 +1,25 @@
 use std::borrow::Cow;
 
-#[allow(dead_code)]
 pub(crate) fn parse_token(input: &str) -> (Option<&str>, &str) {
-    let mut end_of_token = 0;
-    for (i, c) in input.char_indices() {
-        if tchar(c) {
-            end_of_token = i;
-        } else {
-            break;
-        }
-    }
+    let end_of_token = input.find(|c| !tchar(c)).unwrap_or(input.len());
 
     if end_of_token == 0 {
         (None, input)
     } else {
-        (Some(&input[..end_of_token + 1]), &input[end_of_token + 1..])
+        (Some(&input[..end_of_token]), &input[end_of_token..])
     }
 }
 
+pub(crate) fn is_token(input: &str) -> bool {
+    matches!(parse_token(input), (Some(_), ""))
+}
+
+pub(crate) fn is_field_text(input: &str) -> bool {
+    input
+        .bytes()
+        .all(|b| b == b'\t' || (b' '..=b'~').contains(&b))
+}
+
 fn tchar(c: char) -> bool {
     matches!(
         c, 'a'..='z'
@@ -45,7 +47,6 @@ fn vchar(c: char) -> bool {
     matches!(c as u8, b'\t' | 32..=126 | 128..=255)
 }
 
-#[allow(dead_code)]
 pub(crate) fn parse_quoted_string(input: &str) -> (Option<Cow<'_, str>>, &str) {
     if !input.starts_with('"') {
         return (None, input);
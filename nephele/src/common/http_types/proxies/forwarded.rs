@@ -0,0 +1,285 @@
+use std::borrow::Cow;
+
+use crate::common::http_types::headers::{
+    HeaderName, HeaderValue, Headers, FORWARDED, X_FORWARDED_FOR, X_FORWARDED_HOST,
+    X_FORWARDED_PROTO,
+};
+use crate::common::http_types::parse_utils::{parse_quoted_string, parse_token};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Forwarded<'a> {
+    by: Option<Cow<'a, str>>,
+    forwarded_for: Vec<Cow<'a, str>>,
+    host: Option<Cow<'a, str>>,
+    proto: Option<Cow<'a, str>>,
+}
+
+#[derive(Default)]
+struct Element<'a> {
+    by: Option<Cow<'a, str>>,
+    forwarded_for: Option<Cow<'a, str>>,
+    host: Option<Cow<'a, str>>,
+    proto: Option<Cow<'a, str>>,
+}
+
+impl<'a> Forwarded<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_headers(
+        headers: &'a impl AsRef<Headers>,
+    ) -> crate::common::http_types::Result<Option<Self>> {
+        let headers = headers.as_ref();
+        match headers.get(FORWARDED) {
+            Some(values) => {
+                let mut forwarded = Self::new();
+                for value in values.iter() {
+                    forwarded.parse_list(value.as_str())?;
+                }
+                Ok(Some(forwarded))
+            }
+            None => Ok(Self::from_x_headers(headers)),
+        }
+    }
+
+    fn from_x_headers(headers: &'a Headers) -> Option<Self> {
+        let first = |name: HeaderName| {
+            headers
+                .get(name)
+                .and_then(|values| values.iter().next())
+                .and_then(|value| value.as_str().split(',').next())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(Cow::Borrowed)
+        };
+
+        let forwarded_for = headers
+            .get(X_FORWARDED_FOR)
+            .into_iter()
+            .flat_map(|values| values.iter())
+            .flat_map(|value| value.as_str().split(','))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(Cow::Borrowed)
+            .collect::<Vec<_>>();
+
+        let forwarded = Self {
+            by: None,
+            forwarded_for,
+            host: first(X_FORWARDED_HOST),
+            proto: first(X_FORWARDED_PROTO),
+        };
+
+        if forwarded == Self::default() {
+            None
+        } else {
+            Some(forwarded)
+        }
+    }
+
+    pub fn parse(input: &'a str) -> crate::common::http_types::Result<Self> {
+        let mut forwarded = Self::new();
+        forwarded.parse_list(input)?;
+        Ok(forwarded)
+    }
+
+    fn parse_list(&mut self, mut input: &'a str) -> crate::common::http_types::Result<()> {
+        loop {
+            input = self.parse_element(input)?;
+            match input.strip_prefix(',') {
+                Some(rest) => input = rest,
+                None => break,
+            }
+        }
+
+        crate::ensure_status!(
+            input.is_empty(),
+            400,
+            "Unexpected {:?} in Forwarded header",
+            input
+        );
+        Ok(())
+    }
+
+    fn parse_element(&mut self, mut input: &'a str) -> crate::common::http_types::Result<&'a str> {
+        let mut element = Element::default();
+
+        loop {
+            input = input.trim_start_matches(is_whitespace);
+            if input.is_empty() || input.starts_with(',') {
+                break;
+            }
+
+            let (key, rest) = match parse_token(input) {
+                (Some(key), rest) => (key, rest),
+                (None, _) => crate::bail_status!(400, "Invalid Forwarded parameter {:?}", input),
+            };
+            let rest = match rest.strip_prefix('=') {
+                Some(rest) => rest,
+                None => crate::bail_status!(400, "Missing value for Forwarded parameter {}", key),
+            };
+            let (value, rest) = match parse_value(rest) {
+                (Some(value), rest) => (value, rest),
+                (None, _) => {
+                    crate::bail_status!(400, "Invalid value for Forwarded parameter {}", key)
+                }
+            };
+
+            let slot = if key.eq_ignore_ascii_case("by") {
+                Some(&mut element.by)
+            } else if key.eq_ignore_ascii_case("for") {
+                Some(&mut element.forwarded_for)
+            } else if key.eq_ignore_ascii_case("host") {
+                Some(&mut element.host)
+            } else if key.eq_ignore_ascii_case("proto") {
+                Some(&mut element.proto)
+            } else {
+                None
+            };
+            if let Some(slot) = slot {
+                crate::ensure_status!(slot.is_none(), 400, "Duplicate Forwarded parameter {}", key);
+                *slot = Some(value);
+            }
+
+            input = rest.trim_start_matches(is_whitespace);
+            match input.strip_prefix(';') {
+                Some(rest) => input = rest,
+                None => break,
+            }
+        }
+
+        if let Some(forwarded_for) = element.forwarded_for {
+            self.forwarded_for.push(forwarded_for);
+        }
+        if self.by.is_none() {
+            self.by = element.by;
+        }
+        if self.host.is_none() {
+            self.host = element.host;
+        }
+        if self.proto.is_none() {
+            self.proto = element.proto;
+        }
+
+        Ok(input)
+    }
+
+    pub fn forwarded_for(&self) -> Vec<&str> {
+        self.forwarded_for.iter().map(|s| s.as_ref()).collect()
+    }
+
+    pub fn add_for(&mut self, forwarded_for: impl Into<Cow<'a, str>>) {
+        self.forwarded_for.push(forwarded_for.into());
+    }
+
+    pub fn by(&self) -> Option<&str> {
+        self.by.as_deref()
+    }
+
+    pub fn set_by(&mut self, by: impl Into<Cow<'a, str>>) {
+        self.by = Some(by.into());
+    }
+
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    pub fn set_host(&mut self, host: impl Into<Cow<'a, str>>) {
+        self.host = Some(host.into());
+    }
+
+    pub fn proto(&self) -> Option<&str> {
+        self.proto.as_deref()
+    }
+
+    pub fn set_proto(&mut self, proto: impl Into<Cow<'a, str>>) {
+        self.proto = Some(proto.into());
+    }
+
+    pub(crate) fn borrowed_for(&self) -> Option<&'a str> {
+        self.forwarded_for.first().and_then(borrowed)
+    }
+
+    pub(crate) fn borrowed_host(&self) -> Option<&'a str> {
+        self.host.as_ref().and_then(borrowed)
+    }
+
+    pub fn into_owned(self) -> Forwarded<'static> {
+        let owned = |value: Cow<'a, str>| Cow::Owned(value.into_owned());
+        Forwarded {
+            by: self.by.map(owned),
+            forwarded_for: self.forwarded_for.into_iter().map(owned).collect(),
+            host: self.host.map(owned),
+            proto: self.proto.map(owned),
+        }
+    }
+
+    pub fn apply(&self, mut headers: impl AsMut<Headers>) {
+        headers.as_mut().insert(self.name(), self.value());
+    }
+
+    pub fn name(&self) -> HeaderName {
+        FORWARDED
+    }
+
+    pub fn value(&self) -> HeaderValue {
+        let mut output = String::new();
+        let mut first = vec![];
+        if let Some(by) = &self.by {
+            first.push(("by", by));
+        }
+        if let Some(forwarded_for) = self.forwarded_for.first() {
+            first.push(("for", forwarded_for));
+        }
+        if let Some(host) = &self.host {
+            first.push(("host", host));
+        }
+        if let Some(proto) = &self.proto {
+            first.push(("proto", proto));
+        }
+
+        for (i, (key, value)) in first.into_iter().enumerate() {
+            if i > 0 {
+                output.push(';');
+            }
+            write_pair(&mut output, key, value);
+        }
+        for forwarded_for in self.forwarded_for.iter().skip(1) {
+            output.push_str(", ");
+            write_pair(&mut output, "for", forwarded_for);
+        }
+
+        unsafe { HeaderValue::from_bytes_unchecked(output.into()) }
+    }
+}
+
+fn borrowed<'a>(value: &Cow<'a, str>) -> Option<&'a str> {
+    match value {
+        Cow::Borrowed(value) => Some(value),
+        Cow::Owned(_) => None,
+    }
+}
+
+fn parse_value(input: &str) -> (Option<Cow<'_, str>>, &str) {
+    match parse_token(input) {
+        (Some(token), rest) => (Some(Cow::Borrowed(token)), rest),
+        (None, _) => parse_quoted_string(input),
+    }
+}
+
+fn write_pair(output: &mut String, key: &str, value: &str) {
+    match parse_token(value) {
+        (Some(_), "") => {
+            output.push_str(&format!("{}={}", key, value));
+        }
+        _ => {
+            let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+            output.push_str(&format!("{}=\"{}\"", key, escaped));
+        }
+    }
+}
+
+fn is_whitespace(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
@@ -0,0 +1,3 @@
+mod forwarded;
+
+pub use forwarded::Forwarded;
@@ -8,9 +8,10 @@ use std::task::{Context, Poll};
 use crate::common::http_types::convert::{DeserializeOwned, Serialize};
 use crate::common::http_types::headers::{
     self, HeaderName, HeaderValue, HeaderValues, Headers, Names, ToHeaderValues, Values,
-    CONTENT_TYPE,
+    CONTENT_TYPE, TRAILER,
 };
 use crate::common::http_types::mime::Mime;
+use crate::common::http_types::proxies::Forwarded;
 use crate::common::http_types::trailers::{self, Trailers};
 use crate::common::http_types::{Body, Extensions, Method, StatusCode, Url, Version};
 
@@ -76,33 +77,23 @@ impl Request {
     }
 
     pub fn host(&self) -> Option<&str> {
-        self.forwarded_header_part("host")
-            .or_else(|| {
-                self.header("X-Forwarded-Host")
-                    .and_then(|h| h.as_str().split(',').next())
-            })
+        self.forwarded()
+            .ok()
+            .flatten()
+            .and_then(|forwarded| forwarded.borrowed_host())
             .or_else(|| self.header(&headers::HOST).map(|h| h.as_str()))
             .or_else(|| self.url().host_str())
     }
 
-    fn forwarded_header_part(&self, part: &str) -> Option<&str> {
-        self.header("Forwarded").and_then(|header| {
-            header.as_str().split(';').find_map(|key_equals_value| {
-                let parts = key_equals_value.split('=').collect::<Vec<_>>();
-                if parts.len() == 2 && parts[0].eq_ignore_ascii_case(part) {
-                    Some(parts[1])
-                } else {
-                    None
-                }
-            })
-        })
+    pub fn forwarded(&self) -> crate::common::http_types::Result<Option<Forwarded<'_>>> {
+        Forwarded::from_headers(self)
     }
 
     fn forwarded_for(&self) -> Option<&str> {
-        self.forwarded_header_part("for").or_else(|| {
-            self.header("X-Forwarded-For")
-                .and_then(|header| header.as_str().split(',').next())
-        })
+        self.forwarded()
+            .ok()
+            .flatten()
+            .and_then(|forwarded| forwarded.borrowed_for())
     }
 
     pub fn method(&self) -> Method {
@@ -236,6 +227,10 @@ impl Request {
         self.has_trailers
     }
 
+    pub fn declare_trailer(&mut self, name: impl Into<HeaderName>) {
+        self.headers.append(TRAILER, name.into().as_str());
+    }
+
     pub fn iter(&self) -> headers::Iter<'_> {
         self.headers.iter()
     }
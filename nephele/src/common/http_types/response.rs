@@ -9,7 +9,7 @@ use std::task::{Context, Poll};
 use crate::common::http_types::convert::DeserializeOwned;
 use crate::common::http_types::headers::{
     self, HeaderName, HeaderValue, HeaderValues, Headers, Names, ToHeaderValues, Values,
-    CONTENT_TYPE,
+    CONTENT_TYPE, TRAILER,
 };
 use crate::common::http_types::mime::Mime;
 use crate::common::http_types::trailers::{self, Trailers};
@@ -204,6 +204,10 @@ impl Response {
         self.has_trailers
     }
 
+    pub fn declare_trailer(&mut self, name: impl Into<HeaderName>) {
+        self.headers.append(TRAILER, name.into().as_str());
+    }
+
     #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
     pub fn send_upgrade(&mut self) -> upgrade::Sender {
         self.has_upgrade = true;
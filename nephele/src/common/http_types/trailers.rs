@@ -130,6 +130,10 @@ impl Receiver {
     pub(crate) fn new(receiver: cynthia::platform::channel::Receiver<Trailers>) -> Self {
         Self { receiver }
     }
+
+    pub(crate) fn try_recv(&mut self) -> Option<Trailers> {
+        self.receiver.try_recv().ok()
+    }
 }
 
 impl Future for Receiver {
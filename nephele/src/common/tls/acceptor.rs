@@ -17,7 +17,19 @@ pub enum Error {
 }
 
 impl TlsAcceptor {
-    pub async fn new<R, S>(mut file: R, password: S) -> Result<Self, Error>
+    pub async fn new<R, S>(file: R, password: S) -> Result<Self, Error>
+    where
+        R: AsyncRead + Unpin,
+        S: AsRef<str>,
+    {
+        Self::with_alpn(file, password, &[]).await
+    }
+
+    pub async fn with_alpn<R, S>(
+        mut file: R,
+        password: S,
+        protocols: &[&str],
+    ) -> Result<Self, Error>
     where
         R: AsyncRead + Unpin,
         S: AsRef<str>,
@@ -26,7 +38,9 @@ impl TlsAcceptor {
         file.read_to_end(&mut identity).await?;
 
         let identity = native_tls::Identity::from_pkcs12(&identity, password.as_ref())?;
-        Ok(TlsAcceptor(native_tls::TlsAcceptor::new(identity)?))
+        let mut builder = native_tls::TlsAcceptor::builder(identity);
+        builder.accept_alpn(protocols);
+        Ok(TlsAcceptor(builder.build()?))
     }
 
     pub async fn accept<S>(&self, stream: S) -> Result<TlsStream<S>, native_tls::Error>
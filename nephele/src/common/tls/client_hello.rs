@@ -0,0 +1,110 @@
+use std::io;
+use std::str;
+
+use crate::common::tls::runtime::{AsyncRead, AsyncReadExt};
+
+const RECORD_HEADER_LEN: usize = 5;
+const HANDSHAKE_HEADER_LEN: usize = 4;
+const VERSION_AND_RANDOM_LEN: usize = 34;
+const MAX_CLIENT_HELLO_LEN: usize = 64 * 1024;
+const CONTENT_TYPE_HANDSHAKE: u8 = 0x16;
+const HANDSHAKE_CLIENT_HELLO: u8 = 0x01;
+const EXTENSION_SERVER_NAME: u16 = 0x0000;
+const NAME_TYPE_HOST_NAME: u8 = 0x00;
+
+pub(crate) async fn read_client_hello<S>(io: &mut S) -> io::Result<Vec<u8>>
+where
+    S: AsyncRead + Unpin,
+{
+    let mut buf = Vec::new();
+    loop {
+        let start = buf.len();
+        buf.resize(start + RECORD_HEADER_LEN, 0);
+        io.read_exact(&mut buf[start..]).await?;
+        if buf[start] != CONTENT_TYPE_HANDSHAKE {
+            return Ok(buf);
+        }
+
+        let len = u16::from_be_bytes([buf[start + 3], buf[start + 4]]) as usize;
+        buf.resize(start + RECORD_HEADER_LEN + len, 0);
+        io.read_exact(&mut buf[start + RECORD_HEADER_LEN..]).await?;
+        if len == 0 || buf.len() >= MAX_CLIENT_HELLO_LEN || handshake(&buf).is_some() {
+            return Ok(buf);
+        }
+    }
+}
+
+pub(crate) fn server_name(records: &[u8]) -> Option<String> {
+    let hello = handshake(records)?;
+    let (header, body) = split(&hello, HANDSHAKE_HEADER_LEN)?;
+    if header[0] != HANDSHAKE_CLIENT_HELLO {
+        return None;
+    }
+    let (_, body) = split(body, VERSION_AND_RANDOM_LEN)?;
+    let (_session_id, body) = prefixed(body, 1)?;
+    let (_cipher_suites, body) = prefixed(body, 2)?;
+    let (_compression_methods, body) = prefixed(body, 1)?;
+    let (mut extensions, _) = prefixed(body, 2)?;
+
+    while !extensions.is_empty() {
+        let (kind, rest) = split(extensions, 2)?;
+        let (data, rest) = prefixed(rest, 2)?;
+        extensions = rest;
+        if u16::from_be_bytes([kind[0], kind[1]]) == EXTENSION_SERVER_NAME {
+            return host_name(data);
+        }
+    }
+    None
+}
+
+fn handshake(mut records: &[u8]) -> Option<Vec<u8>> {
+    let mut handshake = Vec::new();
+    while !records.is_empty() {
+        let (header, rest) = split(records, 3)?;
+        if header[0] != CONTENT_TYPE_HANDSHAKE {
+            return None;
+        }
+        let (fragment, rest) = prefixed(rest, 2)?;
+        handshake.extend_from_slice(fragment);
+        records = rest;
+
+        if let Some((header, _)) = split(&handshake, HANDSHAKE_HEADER_LEN) {
+            let len = HANDSHAKE_HEADER_LEN + prefixed_len(&header[1..]);
+            if handshake.len() >= len {
+                handshake.truncate(len);
+                return Some(handshake);
+            }
+        }
+    }
+    None
+}
+
+fn host_name(data: &[u8]) -> Option<String> {
+    let (mut names, _) = prefixed(data, 2)?;
+    while !names.is_empty() {
+        let (kind, rest) = split(names, 1)?;
+        let (name, rest) = prefixed(rest, 2)?;
+        names = rest;
+        if kind[0] == NAME_TYPE_HOST_NAME {
+            return str::from_utf8(name).ok().map(str::to_owned);
+        }
+    }
+    None
+}
+
+fn split(buf: &[u8], len: usize) -> Option<(&[u8], &[u8])> {
+    if buf.len() < len {
+        None
+    } else {
+        Some(buf.split_at(len))
+    }
+}
+
+fn prefixed(buf: &[u8], width: usize) -> Option<(&[u8], &[u8])> {
+    let (prefix, rest) = split(buf, width)?;
+    split(rest, prefixed_len(prefix))
+}
+
+fn prefixed_len(prefix: &[u8]) -> usize {
+    prefix.iter().fold(0, |len, b| len << 8 | *b as usize)
+}
@@ -1,6 +1,7 @@
 #![warn(missing_debug_implementations)]
 
 mod acceptor;
+mod client_hello;
 mod connector;
 mod handshake;
 mod runtime;
@@ -9,6 +10,7 @@ mod tls_stream;
 
 pub use accept::accept;
 pub use acceptor::{Error as AcceptError, TlsAcceptor};
+pub(crate) use client_hello::{read_client_hello, server_name};
 pub use connect::{connect, TlsConnector};
 pub use host::Host;
 pub use tls_stream::TlsStream;
@@ -149,6 +151,11 @@ mod connect {
             self
         }
 
+        pub fn request_alpns(mut self, protocols: &[&str]) -> Self {
+            self.builder.request_alpns(protocols);
+            self
+        }
+
         pub fn use_sni(mut self, use_sni: bool) -> Self {
             self.builder.use_sni(use_sni);
             self
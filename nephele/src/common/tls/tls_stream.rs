@@ -54,6 +54,13 @@ impl<S> TlsStream<S> {
         self.0.peer_certificate()
     }
 
+    pub fn negotiated_alpn(&self) -> crate::common::tls::Result<Option<Vec<u8>>>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        self.0.negotiated_alpn()
+    }
+
     pub fn tls_server_end_point(&self) -> crate::common::tls::Result<Option<Vec<u8>>>
     where
         S: AsyncRead + AsyncWrite + Unpin,
@@ -2,3 +2,4 @@ pub mod common;
 pub use common::{http_types, tls};
 
 pub mod proto;
+pub mod server;
@@ -0,0 +1,120 @@
+use cynthia::future::swap::{AsyncRead, AsyncWrite};
+use std::net::SocketAddr;
+
+use crate::common::http_types::Request;
+use crate::common::tls::TlsStream;
+
+#[derive(Clone, Debug, Default)]
+pub struct ConnectionInfo {
+    peer_addr: Option<SocketAddr>,
+    local_addr: Option<SocketAddr>,
+    tls: Option<TlsInfo>,
+}
+
+impl ConnectionInfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_peer_addr(mut self, addr: SocketAddr) -> Self {
+        self.peer_addr = Some(addr);
+        self
+    }
+
+    pub fn with_local_addr(mut self, addr: SocketAddr) -> Self {
+        self.local_addr = Some(addr);
+        self
+    }
+
+    pub fn with_tls(mut self, tls: TlsInfo) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.peer_addr
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.local_addr
+    }
+
+    pub fn tls(&self) -> Option<&TlsInfo> {
+        self.tls.as_ref()
+    }
+
+    pub(crate) fn take_tls(&mut self) -> Option<TlsInfo> {
+        self.tls.take()
+    }
+
+    pub(crate) fn apply(&self, req: &mut Request) {
+        if self.peer_addr.is_some() {
+            req.set_peer_addr(self.peer_addr);
+        }
+        if self.local_addr.is_some() {
+            req.set_local_addr(self.local_addr);
+        }
+        if self.tls.is_some() && req.url().scheme() == "http" {
+            let _ = req.url_mut().set_scheme("https");
+        }
+        req.ext_mut().insert(self.clone());
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TlsInfo {
+    server_name: Option<String>,
+    peer_certificate: Option<Vec<u8>>,
+    alpn_protocol: Option<Vec<u8>>,
+}
+
+impl TlsInfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_server_name(mut self, name: impl Into<String>) -> Self {
+        self.server_name = Some(name.into());
+        self
+    }
+
+    pub fn with_peer_certificate(mut self, der: Vec<u8>) -> Self {
+        self.peer_certificate = Some(der);
+        self
+    }
+
+    pub fn with_alpn_protocol(mut self, protocol: Vec<u8>) -> Self {
+        self.alpn_protocol = Some(protocol);
+        self
+    }
+
+    pub fn server_name(&self) -> Option<&str> {
+        self.server_name.as_deref()
+    }
+
+    pub fn peer_certificate(&self) -> Option<&[u8]> {
+        self.peer_certificate.as_deref()
+    }
+
+    pub fn alpn_protocol(&self) -> Option<&[u8]> {
+        self.alpn_protocol.as_deref()
+    }
+
+    pub(crate) fn with_stream<S>(
+        mut self,
+        stream: &TlsStream<S>,
+    ) -> crate::common::http_types::Result<Self>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        if self.peer_certificate.is_none() {
+            if let Some(certificate) = stream.peer_certificate()? {
+                self.peer_certificate = Some(certificate.to_der()?);
+            }
+        }
+        if self.alpn_protocol.is_none() {
+            self.alpn_protocol = stream.negotiated_alpn()?;
+        }
+        Ok(self)
+    }
+}
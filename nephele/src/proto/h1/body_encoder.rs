@@ -5,13 +5,14 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use crate::common::http_types::Body;
-use crate::proto::h1::chunked::ChunkedEncoder;
+use crate::proto::h1::chunked::{ChunkedEncoder, PendingTrailers};
 
 #[pin_project(project=BodyEncoderProjection)]
 #[derive(Debug)]
 pub(crate) enum BodyEncoder {
     Chunked(#[pin] ChunkedEncoder<Body>),
     Fixed(#[pin] Body),
+    CloseDelimited(#[pin] Body),
 }
 
 impl BodyEncoder {
@@ -21,6 +22,20 @@ impl BodyEncoder {
             None => Self::Chunked(ChunkedEncoder::new(body)),
         }
     }
+
+    pub(crate) fn with_trailers(body: Body, trailers: Option<PendingTrailers>) -> Self {
+        match trailers {
+            Some(trailers) => Self::Chunked(ChunkedEncoder::with_trailers(body, Some(trailers))),
+            None => Self::new(body),
+        }
+    }
+
+    pub(crate) fn close_delimited(body: Body) -> Self {
+        match body.len() {
+            Some(_) => Self::Fixed(body),
+            None => Self::CloseDelimited(body),
+        }
+    }
 }
 
 impl AsyncRead for BodyEncoder {
@@ -32,6 +47,7 @@ impl AsyncRead for BodyEncoder {
         match self.project() {
             BodyEncoderProjection::Chunked(encoder) => encoder.poll_read(cx, buf),
             BodyEncoderProjection::Fixed(body) => body.poll_read(cx, buf),
+            BodyEncoderProjection::CloseDelimited(body) => body.poll_read(cx, buf),
         }
     }
 }
@@ -25,6 +25,9 @@ pub struct ChunkedDecoder<R: AsyncRead> {
     initial_decode: bool,
     state: State,
     trailer_sender: Option<Sender>,
+    max_len: Option<u64>,
+    declared_len: u64,
+    limit_exceeded: bool,
 }
 
 impl<R: AsyncRead> ChunkedDecoder<R> {
@@ -36,8 +39,28 @@ impl<R: AsyncRead> ChunkedDecoder<R> {
             initial_decode: false,
             state: State::Init,
             trailer_sender: Some(trailer_sender),
+            max_len: None,
+            declared_len: 0,
+            limit_exceeded: false,
         }
     }
+
+    pub(crate) fn with_max_len(mut self, max_len: Option<u64>) -> Self {
+        self.max_len = max_len;
+        self
+    }
+
+    pub(crate) fn limit_exceeded(&self) -> bool {
+        self.limit_exceeded
+    }
+
+    pub(crate) fn buffered(&self) -> &[u8] {
+        &self.buffer[self.current.start..self.current.end]
+    }
+
+    pub(crate) fn get_ref(&self) -> &R {
+        &self.inner
+    }
 }
 
 impl<R: AsyncRead + Unpin> ChunkedDecoder<R> {
@@ -123,7 +146,26 @@ impl<R: AsyncRead + Unpin> ChunkedDecoder<R> {
         buf: &mut [u8],
     ) -> swap::Result<DecodeResult> {
         match self.state {
-            State::Init => decode_init(buffer, pos),
+            State::Init => {
+                let res = decode_init(buffer, pos)?;
+                if let DecodeResult::Some {
+                    new_state: Some(State::Chunk(_, len)),
+                    ..
+                } = res
+                {
+                    self.declared_len = self.declared_len.saturating_add(len);
+                    if let Some(max_len) = self.max_len {
+                        if self.declared_len > max_len {
+                            self.limit_exceeded = true;
+                            return Err(swap::Error::new(
+                                swap::ErrorKind::InvalidData,
+                                "chunked body exceeds the maximum length",
+                            ));
+                        }
+                    }
+                }
+                Ok(res)
+            }
             State::Chunk(current, len) => self.poll_read_chunk(cx, buffer, pos, buf, current, len),
             State::ChunkEnd => decode_chunk_end(buffer, pos),
             State::Trailer => decode_trailer(buffer, pos),
@@ -1,40 +1,101 @@
 use core::task::{Context, Poll};
 use cynthia::future::prelude::*;
-use cynthia::future::swap;
+use cynthia::future::swap::{self, Cursor};
 use futures_core::ready;
+use std::io::Write;
 use std::pin::Pin;
 
+use crate::common::http_types::headers::{
+    HeaderName, HeaderValues, CONTENT_LENGTH, HOST, TRAILER, TRANSFER_ENCODING,
+};
+use crate::common::http_types::trailers::{self, Trailers};
+
+const FORBIDDEN_TRAILERS: [HeaderName; 4] = [CONTENT_LENGTH, HOST, TRAILER, TRANSFER_ENCODING];
+
+#[derive(Debug)]
+pub(crate) struct PendingTrailers {
+    receiver: trailers::Receiver,
+    ready: Option<Trailers>,
+}
+
+impl PendingTrailers {
+    pub(crate) fn new(mut receiver: trailers::Receiver) -> Self {
+        let ready = receiver.try_recv();
+        Self { receiver, ready }
+    }
+
+    pub(crate) fn declared(&self, preset: Option<&HeaderValues>) -> Option<String> {
+        let preset = preset
+            .into_iter()
+            .flat_map(|values| values.iter())
+            .flat_map(|value| value.as_str().split(','))
+            .filter_map(|name| name.trim().parse::<HeaderName>().ok());
+        let ready = self
+            .ready
+            .iter()
+            .flat_map(|trailers| trailers.iter())
+            .map(|(name, _)| name.clone());
+        let mut names = preset
+            .chain(ready)
+            .filter(|name| !name.as_str().is_empty() && !FORBIDDEN_TRAILERS.contains(name))
+            .map(|name| name.as_str().to_owned())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names.dedup();
+
+        if names.is_empty() {
+            None
+        } else {
+            Some(names.join(", "))
+        }
+    }
+
+    fn poll_trailers(&mut self, cx: &mut Context<'_>) -> Poll<Option<Trailers>> {
+        match self.ready.take() {
+            Some(trailers) => Poll::Ready(Some(trailers)),
+            None => Pin::new(&mut self.receiver).poll(cx),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum State {
+    Body,
+    Trailers,
+    Flush(Cursor<Vec<u8>>),
+    Done,
+}
+
 #[derive(Debug)]
 pub(crate) struct ChunkedEncoder<R> {
     reader: R,
-    done: bool,
+    trailers: Option<PendingTrailers>,
+    state: State,
 }
 
 impl<R: AsyncRead + Unpin> ChunkedEncoder<R> {
     pub(crate) fn new(reader: R) -> Self {
+        Self::with_trailers(reader, None)
+    }
+
+    pub(crate) fn with_trailers(reader: R, trailers: Option<PendingTrailers>) -> Self {
         Self {
             reader,
-            done: false,
+            trailers,
+            state: State::Body,
         }
     }
-}
-
-impl<R: AsyncRead + Unpin> AsyncRead for ChunkedEncoder<R> {
-    fn poll_read(
-        mut self: Pin<&mut Self>,
-        cx: &mut Context<'_>,
-        buf: &mut [u8],
-    ) -> Poll<swap::Result<usize>> {
-        if self.done {
-            return Poll::Ready(Ok(0));
-        }
-        let reader = &mut self.reader;
 
+    fn poll_body(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<swap::Result<usize>> {
         let max_bytes_to_read = max_bytes_to_read(buf.len());
 
-        let bytes = ready!(Pin::new(reader).poll_read(cx, &mut buf[..max_bytes_to_read]))?;
+        let bytes =
+            ready!(Pin::new(&mut self.reader).poll_read(cx, &mut buf[..max_bytes_to_read]))?;
         if bytes == 0 {
-            self.done = true;
+            let len = b"0\r\n".len();
+            buf[..len].copy_from_slice(b"0\r\n");
+            self.state = State::Trailers;
+            return Poll::Ready(Ok(len));
         }
         let start = format!("{:X}\r\n", bytes);
         let start_length = start.as_bytes().len();
@@ -44,6 +105,50 @@ impl<R: AsyncRead + Unpin> AsyncRead for ChunkedEncoder<R> {
         buf[total - 2..total].copy_from_slice(b"\r\n");
         Poll::Ready(Ok(total))
     }
+
+    fn poll_trailers(&mut self, cx: &mut Context<'_>) -> Poll<swap::Result<Cursor<Vec<u8>>>> {
+        let trailers = match self.trailers.as_mut() {
+            Some(pending) => ready!(pending.poll_trailers(cx)),
+            None => None,
+        };
+
+        let mut out = Vec::new();
+        if let Some(trailers) = trailers {
+            for (name, values) in trailers.iter() {
+                if FORBIDDEN_TRAILERS.contains(name) {
+                    continue;
+                }
+                for value in values.iter() {
+                    write!(out, "{}: {}\r\n", name, value)?;
+                }
+            }
+        }
+        write!(out, "\r\n")?;
+        Poll::Ready(Ok(Cursor::new(out)))
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for ChunkedEncoder<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<swap::Result<usize>> {
+        loop {
+            self.state = match self.state {
+                State::Body => return self.poll_body(cx, buf),
+                State::Trailers => State::Flush(ready!(self.poll_trailers(cx))?),
+                State::Flush(ref mut cursor) => {
+                    let bytes = ready!(Pin::new(cursor).poll_read(cx, buf))?;
+                    if bytes > 0 {
+                        return Poll::Ready(Ok(bytes));
+                    }
+                    State::Done
+                }
+                State::Done => return Poll::Ready(Ok(0)),
+            }
+        }
+    }
 }
 
 fn max_bytes_to_read(buf_len: usize) -> usize {
@@ -2,4 +2,4 @@ mod decoder;
 mod encoder;
 
 pub(crate) use decoder::ChunkedDecoder;
-pub(crate) use encoder::ChunkedEncoder;
+pub(crate) use encoder::{ChunkedEncoder, PendingTrailers};
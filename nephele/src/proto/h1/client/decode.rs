@@ -1,22 +1,36 @@
 use cynthia::future::prelude::*;
 use cynthia::future::swap::{AsyncRead, BufferReader};
+use cynthia::platform::channel::Sender;
 use std::convert::TryFrom;
+use std::io;
 
-use crate::{ensure, ensure_eq, format_err};
+use crate::{ensure, format_err};
 
 use crate::common::http_types::{
     headers::{CONTENT_LENGTH, DATE, TRANSFER_ENCODING},
-    Body, Response, StatusCode,
+    Body, Method, Response, StatusCode, Version,
 };
 
 use crate::proto::h1::chunked::ChunkedDecoder;
 use crate::proto::h1::date::fmt_http_date;
-use crate::proto::h1::{MAX_HEADERS, MAX_HEAD_LENGTH};
+use crate::proto::h1::eof_notifier::EofNotifier;
+use crate::proto::h1::{has_connection_token, MAX_HEADERS, MAX_HEAD_LENGTH};
 
 const CR: u8 = b'\r';
 const LF: u8 = b'\n';
 
-pub async fn decode<R>(reader: R) -> crate::common::http_types::Result<Response>
+pub async fn decode<R>(reader: R, method: Method) -> crate::common::http_types::Result<Response>
+where
+    R: AsyncRead + Unpin + Send + Sync + 'static,
+{
+    decode_with(reader, method, None).await
+}
+
+pub(crate) async fn decode_with<R>(
+    reader: R,
+    method: Method,
+    mut done: Option<Sender<()>>,
+) -> crate::common::http_types::Result<Response>
 where
     R: AsyncRead + Unpin + Send + Sync + 'static,
 {
@@ -27,7 +41,10 @@ where
 
     loop {
         let bytes_read = reader.read_until(LF, &mut buf).await?;
-        assert!(bytes_read != 0, "Empty response");
+        if bytes_read == 0 && buf.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "Empty response").into());
+        }
+        ensure!(bytes_read != 0, "Empty response");
 
         ensure!(
             buf.len() < MAX_HEAD_LENGTH,
@@ -51,9 +68,14 @@ where
 
     let version = httparse_res.version;
     let version = version.ok_or_else(|| format_err!("No version found"))?;
-    ensure_eq!(version, 1, "Unsupported HTTP version");
+    let version = match version {
+        0 => Version::Http1_0,
+        1 => Version::Http1_1,
+        _ => crate::bail!("Unsupported HTTP version"),
+    };
 
     let mut res = Response::new(StatusCode::try_from(code)?);
+    res.set_version(Some(version));
     for header in httparse_res.headers.iter() {
         res.append_header(header.name, std::str::from_utf8(header.value)?);
     }
@@ -63,6 +85,20 @@ where
         res.insert_header(DATE, &format!("date: {}\r\n", date)[..]);
     }
 
+    let keep_alive = match version {
+        Version::Http1_0 => has_connection_token(&res, "keep-alive"),
+        _ => !has_connection_token(&res, "close"),
+    };
+    let tunnel = is_tunnel(method, res.status());
+    if !keep_alive || tunnel {
+        done = None;
+    }
+
+    if tunnel {
+        res.set_body(Body::from_bytes(reader.buffer().to_vec()));
+        return Ok(res);
+    }
+
     let content_length = res.header(CONTENT_LENGTH);
     let transfer_encoding = res.header(TRANSFER_ENCODING);
 
@@ -71,10 +107,18 @@ where
         "Unexpected Content-Length header"
     );
 
+    if method == Method::Head || !has_body(res.status()) {
+        if reader.buffer().is_empty() {
+            notify(done);
+        }
+        return Ok(res);
+    }
+
     if let Some(encoding) = transfer_encoding {
         if encoding.last().as_str() == "chunked" {
             let trailers_sender = res.send_trailers();
             let reader = BufferReader::new(ChunkedDecoder::new(reader, trailers_sender));
+            let reader = EofNotifier::new(reader, done, None);
             res.set_body(Body::from_reader(reader, None));
 
             return Ok(res);
@@ -83,8 +127,27 @@ where
 
     if let Some(len) = content_length {
         let len = len.last().as_str().parse::<usize>()?;
-        res.set_body(Body::from_reader(reader.take(len as u64), Some(len)));
+        let reader = EofNotifier::new(reader.take(len as u64), done, Some(len as u64));
+        res.set_body(Body::from_reader(reader, Some(len)));
+    } else {
+        res.set_body(Body::from_reader(reader, None));
     }
 
     Ok(res)
 }
+
+pub(crate) fn is_tunnel(method: Method, status: StatusCode) -> bool {
+    status == StatusCode::SwitchingProtocols || (method == Method::Connect && status.is_success())
+}
+
+fn notify(done: Option<Sender<()>>) {
+    if let Some(sender) = done {
+        sender.try_send(()).ok();
+    }
+}
+
+fn has_body(status: StatusCode) -> bool {
+    !(status.is_informational()
+        || status == StatusCode::NoContent
+        || status == StatusCode::NotModified)
+}
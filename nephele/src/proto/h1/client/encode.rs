@@ -3,9 +3,12 @@ use cynthia::runtime::task::{Context, Poll};
 use std::io::Write;
 use std::pin::Pin;
 
-use crate::common::http_types::headers::{CONTENT_LENGTH, HOST, TRANSFER_ENCODING};
+use crate::common::http_types::headers::{
+    CONTENT_LENGTH, HOST, PROXY_CONNECTION, TRAILER, TRANSFER_ENCODING,
+};
 use crate::common::http_types::{Method, Request};
 use crate::proto::h1::body_encoder::BodyEncoder;
+use crate::proto::h1::chunked::PendingTrailers;
 use crate::proto::h1::EncoderState;
 use crate::read_to_end;
 
@@ -14,6 +17,7 @@ use crate::read_to_end;
 pub struct Encoder {
     request: Request,
     state: EncoderState,
+    trailers: Option<PendingTrailers>,
 }
 
 impl Encoder {
@@ -21,6 +25,7 @@ impl Encoder {
         Self {
             request,
             state: EncoderState::Start,
+            trailers: None,
         }
     }
 
@@ -41,10 +46,14 @@ impl Encoder {
         }
 
         if self.request.method() == Method::Connect {
-            self.request.insert_header("proxy-connection", "keep-alive");
+            self.request.insert_header(PROXY_CONNECTION, "keep-alive");
         }
 
-        if let Some(len) = self.request.len() {
+        if self.request.has_trailers() {
+            self.request.remove_header(CONTENT_LENGTH);
+            self.request.insert_header(TRANSFER_ENCODING, "chunked");
+            self.declare_trailers();
+        } else if let Some(len) = self.request.len() {
             self.request.insert_header(CONTENT_LENGTH, len.to_string());
         } else {
             self.request.insert_header(TRANSFER_ENCODING, "chunked");
@@ -53,6 +62,15 @@ impl Encoder {
         Ok(())
     }
 
+    fn declare_trailers(&mut self) {
+        let pending = PendingTrailers::new(self.request.recv_trailers());
+        match pending.declared(self.request.header(TRAILER)) {
+            Some(names) => self.request.insert_header(TRAILER, names),
+            None => self.request.remove_header(TRAILER),
+        };
+        self.trailers = Some(pending);
+    }
+
     fn compute_head(&mut self) -> swap::Result<Cursor<Vec<u8>>> {
         let mut buf = Vec::with_capacity(128);
         let url = self.request.url();
@@ -107,7 +125,9 @@ impl AsyncRead for Encoder {
 
                 EncoderState::Head(ref mut cursor) => {
                     read_to_end!(Pin::new(cursor).poll_read(cx, buf));
-                    EncoderState::Body(BodyEncoder::new(self.request.take_body()))
+                    let body = self.request.take_body();
+                    let trailers = self.trailers.take();
+                    EncoderState::Body(BodyEncoder::with_trailers(body, trailers))
                 }
 
                 EncoderState::Body(ref mut encoder) => {
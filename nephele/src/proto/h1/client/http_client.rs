@@ -0,0 +1,316 @@
+use bytes::Bytes;
+use cynthia::platform::dup::{Arc, Mutex};
+use cynthia::runtime::Async;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::net::TcpStream;
+
+use crate::common::http_types::content::{ContentEncoding, Encoding};
+use crate::common::http_types::headers::{
+    HeaderName, Headers, ToHeaderValues, ACCEPT_ENCODING, AUTHORIZATION, CONNECTION,
+    CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, HOST, HTTP2_SETTINGS, LOCATION,
+    PROXY_AUTHORIZATION, TRANSFER_ENCODING, UPGRADE,
+};
+use crate::common::http_types::upgrade::Connection;
+use crate::common::http_types::{Body, Method, Request, Response, StatusCode, Url};
+use crate::common::tls::TlsConnector;
+use crate::proto::h1::client::{Pool, PoolOptions};
+use crate::proto::h2::client::SendRequest;
+use crate::proto::h2::{self, h2c};
+use crate::{bail, ensure, format_err};
+
+const MAX_REDIRECTS: usize = 10;
+const MAX_REPLAY_BODY_LENGTH: usize = 64 * 1024;
+const ACCEPTED_ENCODINGS: &str = "gzip, deflate, br";
+
+type Stream = Arc<Mutex<Connection>>;
+
+pub struct Client {
+    pool: Pool<Stream>,
+    tls: std::sync::Arc<TlsConnector>,
+    headers: Headers,
+    max_redirects: usize,
+    h2c: bool,
+    decompress: bool,
+    sessions: std::sync::Arc<std::sync::Mutex<HashMap<String, SendRequest<Bytes>>>>,
+}
+
+impl Client {
+    pub fn new() -> Self {
+        Self {
+            pool: Pool::default(),
+            tls: std::sync::Arc::new(TlsConnector::new()),
+            headers: Headers::new(),
+            max_redirects: MAX_REDIRECTS,
+            h2c: false,
+            decompress: true,
+            sessions: Default::default(),
+        }
+    }
+
+    pub fn pool_options(mut self, opts: PoolOptions) -> Self {
+        self.pool = Pool::new(opts);
+        self
+    }
+
+    pub fn tls_connector(mut self, connector: TlsConnector) -> Self {
+        self.tls = std::sync::Arc::new(connector);
+        self
+    }
+
+    pub fn max_redirects(mut self, max: usize) -> Self {
+        self.max_redirects = max;
+        self
+    }
+
+    pub fn h2c(mut self, enabled: bool) -> Self {
+        self.h2c = enabled;
+        self
+    }
+
+    pub fn decompress(mut self, enabled: bool) -> Self {
+        self.decompress = enabled;
+        self
+    }
+
+    pub fn default_header(
+        mut self,
+        name: impl Into<HeaderName>,
+        values: impl ToHeaderValues,
+    ) -> Self {
+        self.headers.insert(name, values);
+        self
+    }
+
+    pub async fn send(&self, mut req: Request) -> crate::common::http_types::Result<Response> {
+        for (name, values) in self.headers.iter() {
+            if req.header(name).is_none() {
+                req.insert_header(name, values);
+            }
+        }
+
+        let decompress = self.decompress && req.header(ACCEPT_ENCODING).is_none();
+        if decompress {
+            req.insert_header(ACCEPT_ENCODING, ACCEPTED_ENCODINGS);
+        }
+
+        let mut body = match req.len() {
+            Some(len) if len > 0 && len <= MAX_REPLAY_BODY_LENGTH && self.max_redirects > 0 => {
+                Some(req.take_body().into_bytes().await?)
+            }
+            _ => None,
+        };
+        let mut replayable = body.is_some() || req.is_empty() == Some(true);
+
+        let mut redirects = 0;
+        loop {
+            let mut next = req.clone();
+            if let Some(body) = &body {
+                req.set_body(Body::from_bytes(body.clone()));
+            }
+
+            let res = self.send_once(req).await?;
+
+            let location = match res.header(LOCATION) {
+                Some(location)
+                    if is_redirect(res.status())
+                        && self.max_redirects > 0
+                        && (replayable || redirects_to_get(next.method(), res.status())) =>
+                {
+                    next.url().join(location.last().as_str())?
+                }
+                _ if decompress => return Ok(decompress_response(res)),
+                _ => return Ok(res),
+            };
+
+            redirects += 1;
+            ensure!(
+                redirects <= self.max_redirects,
+                "Exceeded {} redirects",
+                self.max_redirects
+            );
+
+            replayable |= redirect(&mut next, res.status(), location, &mut body);
+            req = next;
+        }
+    }
+
+    async fn send_once(&self, req: Request) -> crate::common::http_types::Result<Response> {
+        if self.h2c && req.url().scheme() == "http" {
+            return self.send_h2c(req).await;
+        }
+
+        let tls = self.tls.clone();
+        self.pool.send(req, move |url| dial(url, tls)).await
+    }
+
+    async fn send_h2c(&self, mut req: Request) -> crate::common::http_types::Result<Response> {
+        let origin = req.url().origin().ascii_serialization();
+
+        let session = self.sessions.lock().unwrap().get(&origin).cloned();
+        if let Some(session) = session {
+            match session.ready().await {
+                Ok(session) => return h2::send_request(session, req).await,
+                Err(_) => {
+                    self.sessions.lock().unwrap().remove(&origin);
+                }
+            }
+        }
+
+        let mut builder = h2::client::Builder::new();
+        builder.enable_push(false);
+
+        let method = req.method();
+        let connection = match req.header(CONNECTION) {
+            Some(values) => format!("{}, upgrade, http2-settings", values.last()),
+            None => "upgrade, http2-settings".to_owned(),
+        };
+        req.insert_header(CONNECTION, connection);
+        req.insert_header(UPGRADE, "h2c");
+        req.insert_header(HTTP2_SETTINGS, h2c::encode_settings(builder.settings()));
+
+        let tls = self.tls.clone();
+        let mut res = self.pool.send(req, move |url| dial(url, tls)).await?;
+        if res.status() != StatusCode::SwitchingProtocols {
+            return Ok(res);
+        }
+
+        let io = res.recv_upgrade().await.await;
+        let io = io.ok_or_else(|| format_err!("Upgraded connection was not handed off"))?;
+
+        let (mut session, connection) = builder.handshake::<_, Bytes>(io).await?;
+        let response = session.send_upgrade(method == Method::Head)?;
+        cynthia::runtime::spawn(async move {
+            if let Err(e) = connection.await {
+                tracing::debug!("h2c connection error: {}", e);
+            }
+        })
+        .detach();
+
+        self.sessions.lock().unwrap().insert(origin, session);
+
+        h2::response_from_h2(response.await?)
+    }
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for Client {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            tls: self.tls.clone(),
+            headers: self.headers.clone(),
+            max_redirects: self.max_redirects,
+            h2c: self.h2c,
+            decompress: self.decompress,
+            sessions: self.sessions.clone(),
+        }
+    }
+}
+
+impl Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("pool", &self.pool)
+            .field("headers", &self.headers)
+            .field("max_redirects", &self.max_redirects)
+            .field("h2c", &self.h2c)
+            .field("decompress", &self.decompress)
+            .finish()
+    }
+}
+
+async fn dial(
+    url: Url,
+    tls: std::sync::Arc<TlsConnector>,
+) -> crate::common::http_types::Result<Stream> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| format_err!("Missing hostname"))?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| format_err!("Unexpected scheme with no default port"))?;
+
+    let stream = Async::<TcpStream>::connect((host, port)).await?;
+
+    let conn = match url.scheme() {
+        "http" => Connection::new(stream),
+        "https" => Connection::new(tls.connect(&url, stream).await?),
+        scheme => bail!("Unsupported scheme: {}", scheme),
+    };
+
+    Ok(Arc::new(Mutex::new(conn)))
+}
+
+fn decompress_response(mut res: Response) -> Response {
+    let encoding = match ContentEncoding::from_headers(&res) {
+        Ok(Some(encoding)) => encoding.encoding(),
+        _ => return res,
+    };
+    if encoding == Encoding::Identity || res.is_empty() == Some(true) {
+        return res;
+    }
+
+    let body = res.take_body().decompress(encoding);
+    res.set_body(body);
+    res.remove_header(CONTENT_ENCODING);
+    res.remove_header(CONTENT_LENGTH);
+    res
+}
+
+fn is_redirect(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::MovedPermanently
+            | StatusCode::Found
+            | StatusCode::SeeOther
+            | StatusCode::TemporaryRedirect
+            | StatusCode::PermanentRedirect
+    )
+}
+
+fn redirects_to_get(method: Method, status: StatusCode) -> bool {
+    match status {
+        StatusCode::SeeOther => method != Method::Head,
+        StatusCode::MovedPermanently | StatusCode::Found => method == Method::Post,
+        _ => false,
+    }
+}
+
+fn redirect(
+    req: &mut Request,
+    status: StatusCode,
+    location: Url,
+    body: &mut Option<Vec<u8>>,
+) -> bool {
+    let to_get = redirects_to_get(req.method(), status);
+
+    if to_get {
+        req.set_method(Method::Get);
+        req.remove_header(CONTENT_LENGTH);
+        req.remove_header(CONTENT_TYPE);
+        req.remove_header(TRANSFER_ENCODING);
+        *body = None;
+    }
+
+    let url = req.url();
+    let same_origin = url.scheme() == location.scheme()
+        && url.host_str() == location.host_str()
+        && url.port_or_known_default() == location.port_or_known_default();
+
+    if !same_origin {
+        req.remove_header(AUTHORIZATION);
+        req.remove_header(PROXY_AUTHORIZATION);
+        req.remove_header(COOKIE);
+    }
+
+    req.remove_header(HOST);
+    *req.url_mut() = location;
+    to_get
+}
@@ -4,9 +4,13 @@ use crate::common::http_types::{Request, Response};
 
 mod decode;
 mod encode;
+mod http_client;
+mod pool;
 
 pub use decode::decode;
 pub use encode::Encoder;
+pub use http_client::Client;
+pub use pool::{Pool, PoolOptions};
 
 pub async fn connect<RW>(
     mut stream: RW,
@@ -15,11 +19,12 @@ pub async fn connect<RW>(
 where
     RW: AsyncRead + AsyncWrite + Send + Sync + Unpin + 'static,
 {
+    let method = req.method();
     let mut req = Encoder::new(req);
 
     swap::copy(&mut req, &mut stream).await?;
 
-    let res = decode(stream).await?;
+    let res = decode(stream, method).await?;
 
     Ok(res)
 }
@@ -0,0 +1,285 @@
+use cynthia::future::swap::{self, AsyncRead, AsyncWrite};
+use cynthia::future::Future;
+use cynthia::platform::channel::{self, Receiver, Sender};
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::common::http_types::upgrade::Connection;
+use crate::common::http_types::{Request, Response, Url};
+use crate::format_err;
+use crate::proto::h1::client::decode::{decode_with, is_tunnel};
+use crate::proto::h1::client::Encoder;
+use crate::proto::h1::has_connection_token;
+use crate::proto::h1::rewind::Rewind;
+
+const MAX_CONNECTIONS_PER_HOST: usize = 16;
+
+type Key = (String, String, u16);
+
+#[derive(Debug, Clone)]
+pub struct PoolOptions {
+    idle_timeout: Option<Duration>,
+    max_connections_per_host: usize,
+}
+
+impl PoolOptions {
+    pub fn idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    pub fn max_connections_per_host(mut self, max: usize) -> Self {
+        self.max_connections_per_host = max.max(1);
+        self
+    }
+}
+
+impl Default for PoolOptions {
+    fn default() -> Self {
+        Self {
+            idle_timeout: Some(Duration::from_secs(90)),
+            max_connections_per_host: MAX_CONNECTIONS_PER_HOST,
+        }
+    }
+}
+
+pub struct Pool<IO> {
+    opts: PoolOptions,
+    hosts: Arc<Mutex<HashMap<Key, Host<IO>>>>,
+}
+
+type Host<IO> = (Sender<Slot<IO>>, Receiver<Slot<IO>>);
+
+enum Slot<IO> {
+    Vacant,
+    Idle(IO, Instant),
+}
+
+struct Permit<IO>(Option<Sender<Slot<IO>>>);
+
+impl<IO> Permit<IO> {
+    fn release(mut self, io: IO) {
+        if let Some(sender) = self.0.take() {
+            sender.try_send(Slot::Idle(io, Instant::now())).ok();
+        }
+    }
+}
+
+impl<IO> Drop for Permit<IO> {
+    fn drop(&mut self) {
+        if let Some(sender) = self.0.take() {
+            sender.try_send(Slot::Vacant).ok();
+        }
+    }
+}
+
+impl<IO> Debug for Pool<IO> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hosts = self.hosts.lock().map(|hosts| hosts.len()).unwrap_or(0);
+        f.debug_struct("Pool")
+            .field("opts", &self.opts)
+            .field("hosts", &hosts)
+            .finish()
+    }
+}
+
+impl<IO> Clone for Pool<IO> {
+    fn clone(&self) -> Self {
+        Self {
+            opts: self.opts.clone(),
+            hosts: self.hosts.clone(),
+        }
+    }
+}
+
+impl<IO> Default for Pool<IO> {
+    fn default() -> Self {
+        Self::new(PoolOptions::default())
+    }
+}
+
+impl<IO> Pool<IO> {
+    pub fn new(opts: PoolOptions) -> Self {
+        Self {
+            opts,
+            hosts: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn is_expired(&self, since: Instant) -> bool {
+        self.opts
+            .idle_timeout
+            .map(|timeout| since.elapsed() >= timeout)
+            .unwrap_or(false)
+    }
+
+    fn evict_expired(&self, hosts: &mut HashMap<Key, Host<IO>>) {
+        hosts.retain(|_, (sender, receiver)| {
+            if sender.sender_count() > 1 || receiver.receiver_count() > 1 {
+                return true;
+            }
+
+            let mut vacant = 0;
+            for _ in 0..receiver.len() {
+                let slot = match receiver.try_recv() {
+                    Ok(Slot::Idle(io, since)) if !self.is_expired(since) => Slot::Idle(io, since),
+                    Ok(_) => {
+                        vacant += 1;
+                        Slot::Vacant
+                    }
+                    Err(_) => break,
+                };
+                sender.try_send(slot).ok();
+            }
+            vacant < self.opts.max_connections_per_host
+        });
+    }
+
+    fn take_idle(&self, (sender, receiver): &Host<IO>) -> Option<IO> {
+        let mut idle = None;
+        for _ in 0..receiver.len() {
+            let slot = match receiver.try_recv() {
+                Ok(Slot::Idle(io, since)) if idle.is_none() && !self.is_expired(since) => {
+                    idle = Some(io);
+                    continue;
+                }
+                Ok(slot) => slot,
+                Err(_) => break,
+            };
+            sender.try_send(slot).ok();
+        }
+        idle
+    }
+
+    async fn checkout(&self, key: &Key) -> (Option<IO>, Permit<IO>) {
+        let ((sender, receiver), idle) = {
+            let mut hosts = self.hosts.lock().unwrap();
+            self.evict_expired(&mut hosts);
+            let max = self.opts.max_connections_per_host;
+            let host = hosts.entry(key.clone()).or_insert_with(|| {
+                let (sender, receiver) = channel::bounded(max);
+                for _ in 0..max {
+                    sender.try_send(Slot::Vacant).ok();
+                }
+                (sender, receiver)
+            });
+            (host.clone(), self.take_idle(host))
+        };
+
+        if let Some(io) = idle {
+            return (Some(io), Permit(Some(sender)));
+        }
+
+        let slot = receiver.recv().await;
+        let permit = Permit(Some(sender));
+        match slot {
+            Ok(Slot::Idle(io, since)) if !self.is_expired(since) => (Some(io), permit),
+            _ => (None, permit),
+        }
+    }
+}
+
+impl<IO> Pool<IO>
+where
+    IO: AsyncRead + AsyncWrite + Clone + Send + Sync + Unpin + 'static,
+{
+    pub async fn send<C, Fut>(
+        &self,
+        req: Request,
+        connect: C,
+    ) -> crate::common::http_types::Result<Response>
+    where
+        C: FnOnce(Url) -> Fut,
+        Fut: Future<Output = crate::common::http_types::Result<IO>>,
+    {
+        let key = pool_key(req.url())?;
+        let url = req.url().clone();
+        let method = req.method();
+
+        let (io, permit) = self.checkout(&key).await;
+        let (io, (mut res, receiver)) = match io {
+            Some(io) => {
+                let retry = match req.is_empty() {
+                    Some(true) if method.is_idempotent() && !req.has_trailers() => {
+                        Some(req.clone())
+                    }
+                    _ => None,
+                };
+                match (exchange(io.clone(), req).await, retry) {
+                    (Err(e), Some(retry)) if is_stale(&e) => {
+                        tracing::debug!("pooled connection closed by peer; retrying: {}", e);
+                        let io = connect(url).await?;
+                        (io.clone(), exchange(io, retry).await?)
+                    }
+                    (exchanged, _) => (io, exchanged?),
+                }
+            }
+            None => {
+                let io = connect(url).await?;
+                (io.clone(), exchange(io, req).await?)
+            }
+        };
+
+        if is_tunnel(method, res.status()) {
+            let buffered = res.take_body().into_bytes().await?;
+            let io = Rewind::new_buffered(io, buffered);
+            res.send_upgrade().send(Connection::new(io)).await;
+            return Ok(res);
+        }
+
+        cynthia::runtime::spawn(async move {
+            if let Ok(()) = receiver.recv().await {
+                permit.release(io);
+            }
+        })
+        .detach();
+
+        Ok(res)
+    }
+}
+
+async fn exchange<IO>(
+    io: IO,
+    req: Request,
+) -> crate::common::http_types::Result<(Response, Receiver<()>)>
+where
+    IO: AsyncRead + AsyncWrite + Clone + Send + Sync + Unpin + 'static,
+{
+    let method = req.method();
+    let close = has_connection_token(&req, "close");
+
+    let mut encoder = Encoder::new(req);
+    swap::copy(&mut encoder, &mut io.clone()).await?;
+
+    let (sender, receiver) = channel::bounded(1);
+    let done = if close { None } else { Some(sender) };
+
+    let res = decode_with(io, method, done).await?;
+    Ok((res, receiver))
+}
+
+fn is_stale(e: &crate::common::http_types::Error) -> bool {
+    e.downcast_ref::<io::Error>().map_or(false, |e| {
+        matches!(
+            e.kind(),
+            io::ErrorKind::UnexpectedEof
+                | io::ErrorKind::ConnectionReset
+                | io::ErrorKind::ConnectionAborted
+                | io::ErrorKind::BrokenPipe
+        )
+    })
+}
+
+fn pool_key(url: &Url) -> crate::common::http_types::Result<Key> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| format_err!("Missing hostname"))?;
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| format_err!("Unexpected scheme with no default port"))?;
+
+    Ok((url.scheme().to_owned(), host.to_owned(), port))
+}
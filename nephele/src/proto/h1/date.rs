@@ -23,7 +23,6 @@ pub struct HttpDate {
     week_day: u8,
 }
 
-#[allow(dead_code)]
 pub(crate) fn parse_http_date(s: &str) -> crate::common::http_types::Result<SystemTime> {
     s.parse::<HttpDate>().map(|d| d.into())
 }
@@ -0,0 +1,114 @@
+use cynthia::future::swap::{self, AsyncBufRead, AsyncRead, BufferReader, Take};
+use cynthia::platform::channel::Sender;
+use futures_core::ready;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::proto::h1::chunked::ChunkedDecoder;
+
+pub(crate) trait Drained {
+    fn is_drained(&self) -> bool;
+}
+
+impl<R> Drained for Take<BufferReader<R>> {
+    fn is_drained(&self) -> bool {
+        self.get_ref().buffer().is_empty()
+    }
+}
+
+impl<R: AsyncRead> Drained for BufferReader<ChunkedDecoder<BufferReader<R>>> {
+    fn is_drained(&self) -> bool {
+        let decoder = self.get_ref();
+        self.buffer().is_empty()
+            && decoder.buffered().is_empty()
+            && decoder.get_ref().buffer().is_empty()
+    }
+}
+
+#[pin_project::pin_project]
+pub(crate) struct EofNotifier<B> {
+    #[pin]
+    reader: B,
+    sender: Option<Sender<()>>,
+    remaining: Option<u64>,
+}
+
+impl<B> fmt::Debug for EofNotifier<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EofNotifier")
+            .field("notified", &self.sender.is_none())
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
+
+impl<B: Drained> EofNotifier<B> {
+    pub(crate) fn new(reader: B, mut sender: Option<Sender<()>>, len: Option<u64>) -> Self {
+        if len == Some(0) {
+            advance(&mut sender, &mut Some(0), 0, false, reader.is_drained());
+        }
+
+        Self {
+            reader,
+            sender,
+            remaining: len,
+        }
+    }
+}
+
+fn advance(
+    sender: &mut Option<Sender<()>>,
+    remaining: &mut Option<u64>,
+    amt: usize,
+    requested: bool,
+    drained: bool,
+) {
+    let complete = match remaining {
+        Some(remaining) => {
+            *remaining = remaining.saturating_sub(amt as u64);
+            *remaining == 0
+        }
+        None => amt == 0 && requested,
+    };
+
+    if complete {
+        if let Some(sender) = sender.take().filter(|_| drained) {
+            sender.try_send(()).ok();
+        }
+    } else if amt == 0 && requested {
+        sender.take();
+    }
+}
+
+impl<B: AsyncBufRead + Drained> AsyncBufRead for EofNotifier<B> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<swap::Result<&[u8]>> {
+        let mut this = self.project();
+        if ready!(this.reader.as_mut().poll_fill_buf(cx))?.is_empty() {
+            let drained = this.reader.is_drained();
+            advance(this.sender, this.remaining, 0, true, drained);
+        }
+        this.reader.poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let mut this = self.project();
+        this.reader.as_mut().consume(amt);
+        let drained = this.reader.is_drained();
+        advance(this.sender, this.remaining, amt, false, drained);
+    }
+}
+
+impl<B: AsyncRead + Drained> AsyncRead for EofNotifier<B> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<swap::Result<usize>> {
+        let mut this = self.project();
+        let n = ready!(this.reader.as_mut().poll_read(cx, buf))?;
+        let drained = this.reader.is_drained();
+        advance(this.sender, this.remaining, n, !buf.is_empty(), drained);
+        Poll::Ready(Ok(n))
+    }
+}
@@ -0,0 +1,80 @@
+use cynthia::future::swap::{self, AsyncRead, AsyncWrite, ErrorKind};
+use cynthia::future::Future;
+use cynthia::io::Timer;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct IoTimeout<T> {
+    inner: T,
+    duration: Option<Duration>,
+    timer: Option<Timer>,
+}
+
+impl<T> IoTimeout<T> {
+    pub(crate) fn new(inner: T, duration: Option<Duration>) -> Self {
+        Self {
+            inner,
+            duration,
+            timer: None,
+        }
+    }
+
+    fn poll_timeout<R>(
+        &mut self,
+        cx: &mut Context<'_>,
+        poll: Poll<swap::Result<R>>,
+    ) -> Poll<swap::Result<R>> {
+        if poll.is_ready() {
+            self.timer = None;
+            return poll;
+        }
+
+        let duration = match self.duration {
+            Some(duration) => duration,
+            None => return Poll::Pending,
+        };
+
+        let timer = self.timer.get_or_insert_with(|| Timer::after(duration));
+        match Pin::new(timer).poll(cx) {
+            Poll::Ready(_) => {
+                self.timer = None;
+                Poll::Ready(Err(ErrorKind::TimedOut.into()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for IoTimeout<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<swap::Result<usize>> {
+        let poll = Pin::new(&mut self.inner).poll_read(cx, buf);
+        self.poll_timeout(cx, poll)
+    }
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for IoTimeout<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<swap::Result<usize>> {
+        let poll = Pin::new(&mut self.inner).poll_write(cx, buf);
+        self.poll_timeout(cx, poll)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<swap::Result<()>> {
+        let poll = Pin::new(&mut self.inner).poll_flush(cx);
+        self.poll_timeout(cx, poll)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<swap::Result<()>> {
+        let poll = Pin::new(&mut self.inner).poll_close(cx);
+        self.poll_timeout(cx, poll)
+    }
+}
@@ -6,16 +6,20 @@ const MAX_HEAD_LENGTH: usize = 8 * 1024;
 
 mod body_encoder;
 mod chunked;
-mod date;
+pub(crate) mod date;
+mod eof_notifier;
+pub(crate) mod io_timeout;
 mod read_notifier;
+pub(crate) mod rewind;
 
 pub mod client;
 pub mod server;
 
+use crate::common::http_types::headers::{Headers, CONNECTION};
 use crate::proto::h1::body_encoder::BodyEncoder;
 use cynthia::future::swap::Cursor;
 
-pub use client::connect;
+pub use client::{connect, Client, Pool, PoolOptions};
 pub use server::{accept, accept_with_opts, ServerOptions};
 
 #[derive(Debug)]
@@ -26,6 +30,21 @@ pub(crate) enum EncoderState {
     End,
 }
 
+pub(crate) fn has_connection_token(headers: impl AsRef<Headers>, token: &str) -> bool {
+    headers
+        .as_ref()
+        .get(CONNECTION)
+        .map(|values| {
+            values.iter().any(|value| {
+                value
+                    .as_str()
+                    .split(',')
+                    .any(|t| t.trim().eq_ignore_ascii_case(token))
+            })
+        })
+        .unwrap_or(false)
+}
+
 #[macro_export]
 macro_rules! read_to_end {
     ($expr:expr) => {
@@ -0,0 +1,60 @@
+use cynthia::future::swap::{self, AsyncRead, AsyncWrite};
+use std::cmp;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+#[derive(Debug, Clone)]
+pub struct Rewind<T> {
+    pre: Vec<u8>,
+    pos: usize,
+    inner: T,
+}
+
+impl<T> Rewind<T> {
+    pub(crate) fn new_buffered(inner: T, pre: Vec<u8>) -> Self {
+        Self { pre, pos: 0, inner }
+    }
+
+    pub(crate) fn buffered(&self) -> &[u8] {
+        &self.pre[self.pos..]
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for Rewind<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<swap::Result<usize>> {
+        if self.pos < self.pre.len() {
+            let n = cmp::min(buf.len(), self.pre.len() - self.pos);
+            buf[..n].copy_from_slice(&self.pre[self.pos..self.pos + n]);
+            self.pos += n;
+            if self.pos == self.pre.len() {
+                self.pre = Vec::new();
+                self.pos = 0;
+            }
+            return Poll::Ready(Ok(n));
+        }
+
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for Rewind<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<swap::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<swap::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<swap::Result<()>> {
+        Pin::new(&mut self.inner).poll_close(cx)
+    }
+}
@@ -1,14 +1,58 @@
 use cynthia::future::swap::{AsyncRead, BufferReader, Take};
 use cynthia::platform::dup::{Arc, Mutex};
-use cynthia::runtime::task::{Context, Poll};
+use cynthia::runtime::task::{Context, Poll, Waker};
+use std::ops::{Deref, DerefMut};
 use std::{fmt::Debug, io, pin::Pin};
 
 use crate::proto::h1::chunked::ChunkedDecoder;
+use crate::proto::h1::rewind::Rewind;
+
+pub(crate) type ConnectionReader<IO> = BufferReader<Rewind<IO>>;
 
 pub enum BodyReader<IO: AsyncRead + Unpin> {
-    Chunked(Arc<Mutex<ChunkedDecoder<BufferReader<IO>>>>),
-    Fixed(Arc<Mutex<Take<BufferReader<IO>>>>),
-    None,
+    Chunked(Arc<Mutex<Shared<ChunkedDecoder<ConnectionReader<IO>>>>>),
+    Fixed(Arc<Mutex<Shared<Take<ConnectionReader<IO>>>>>),
+    None(ConnectionReader<IO>),
+}
+
+impl<IO: AsyncRead + Unpin> BodyReader<IO> {
+    pub(crate) fn limit_exceeded(&self) -> bool {
+        match self {
+            BodyReader::Chunked(r) => r.lock().limit_exceeded(),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn detach(&self) {
+        match self {
+            BodyReader::Chunked(r) => r.lock().detach(),
+            BodyReader::Fixed(r) => r.lock().detach(),
+            BodyReader::None(_) => {}
+        }
+    }
+
+    pub(crate) fn buffered(&self) -> Vec<u8> {
+        match self {
+            BodyReader::Chunked(r) => {
+                let decoder = r.lock();
+                let mut buffered = decoder.buffered().to_vec();
+                buffered.extend_from_slice(decoder.get_ref().buffer());
+                buffered.extend_from_slice(decoder.get_ref().get_ref().buffered());
+                buffered
+            }
+            BodyReader::Fixed(r) => {
+                let reader = r.lock();
+                let mut buffered = reader.get_ref().buffer().to_vec();
+                buffered.extend_from_slice(reader.get_ref().get_ref().buffered());
+                buffered
+            }
+            BodyReader::None(reader) => {
+                let mut buffered = reader.buffer().to_vec();
+                buffered.extend_from_slice(reader.get_ref().buffered());
+                buffered
+            }
+        }
+    }
 }
 
 impl<IO: AsyncRead + Unpin> Debug for BodyReader<IO> {
@@ -16,7 +60,7 @@ impl<IO: AsyncRead + Unpin> Debug for BodyReader<IO> {
         match self {
             BodyReader::Chunked(_) => f.write_str("BodyReader::Chunked"),
             BodyReader::Fixed(_) => f.write_str("BodyReader::Fixed"),
-            BodyReader::None => f.write_str("BodyReader::None"),
+            BodyReader::None(_) => f.write_str("BodyReader::None"),
         }
     }
 }
@@ -30,7 +74,88 @@ impl<IO: AsyncRead + Unpin> AsyncRead for BodyReader<IO> {
         match &*self {
             BodyReader::Chunked(r) => Pin::new(&mut *r.lock()).poll_read(cx, buf),
             BodyReader::Fixed(r) => Pin::new(&mut *r.lock()).poll_read(cx, buf),
-            BodyReader::None => Poll::Ready(Ok(0)),
+            BodyReader::None(_) => Poll::Ready(Ok(0)),
+        }
+    }
+}
+
+pub struct Shared<R> {
+    reader: R,
+    detached: bool,
+    waker: Option<Waker>,
+}
+
+impl<R> Shared<R> {
+    pub(crate) fn new(reader: R) -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Self {
+            reader,
+            detached: false,
+            waker: None,
+        }))
+    }
+
+    fn detach(&mut self) {
+        self.detached = true;
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<R> Deref for Shared<R> {
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        &self.reader
+    }
+}
+
+impl<R> DerefMut for Shared<R> {
+    fn deref_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for Shared<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.reader).poll_read(cx, buf)
+    }
+}
+
+pub struct HandlerReader<R>(Arc<Mutex<Shared<R>>>);
+
+impl<R> HandlerReader<R> {
+    pub(crate) fn new(shared: Arc<Mutex<Shared<R>>>) -> Self {
+        Self(shared)
+    }
+}
+
+impl<R> Debug for HandlerReader<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HandlerReader")
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for HandlerReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut shared = self.0.lock();
+        if shared.detached {
+            let e = io::Error::new(io::ErrorKind::Other, "Request body detached from handler");
+            return Poll::Ready(Err(e));
+        }
+
+        let poll = Pin::new(&mut shared.reader).poll_read(cx, buf);
+        if poll.is_pending() {
+            shared.waker = Some(cx.waker().clone());
         }
+        poll
     }
 }
@@ -1,57 +1,106 @@
 use cynthia::future::prelude::*;
 use cynthia::future::swap::{AsyncRead, AsyncWrite, BufferReader};
-use cynthia::platform::dup::{Arc, Mutex};
+use cynthia::platform::channel::Receiver;
 use std::str::FromStr;
 
 use crate::common::http_types::content::ContentLength;
 use crate::common::http_types::headers::{EXPECT, TRANSFER_ENCODING};
-use crate::common::http_types::{Body, Method, Request, Url};
-use crate::{ensure, ensure_eq, format_err};
+use crate::common::http_types::{Body, Method, Request, Url, Version};
+use crate::format_err;
 
 use crate::proto::h1::chunked::ChunkedDecoder;
+use crate::proto::h1::io_timeout::IoTimeout;
 use crate::proto::h1::read_notifier::ReadNotifier;
-use crate::proto::h1::server::body_reader::BodyReader;
-use crate::proto::h1::{MAX_HEADERS, MAX_HEAD_LENGTH};
+use crate::proto::h1::rewind::Rewind;
+use crate::proto::h1::server::body_reader::{BodyReader, HandlerReader, Shared};
+use crate::proto::h1::server::ServerOptions;
 
 const LF: u8 = b'\n';
 
+const HTTP_1_0_VERSION: u8 = 0;
 const HTTP_1_1_VERSION: u8 = 1;
 
 const CONTINUE_HEADER_VALUE: &str = "100-continue";
-const CONTINUE_RESPONSE: &[u8] = b"HTTP/1.1 100 Continue\r\n\r\n";
+pub(crate) const CONTINUE_RESPONSE: &[u8] = b"HTTP/1.1 100 Continue\r\n\r\n";
 
 pub async fn decode<IO>(
-    mut io: IO,
+    io: IO,
 ) -> crate::common::http_types::Result<Option<(Request, BodyReader<IO>)>>
 where
     IO: AsyncRead + AsyncWrite + Clone + Send + Sync + Unpin + 'static,
 {
-    let mut reader = BufferReader::new(io.clone());
+    let opts = ServerOptions::default();
+    let decoded = decode_buffered(io.clone(), Vec::new(), &opts, &mut None).await?;
+    Ok(decoded.map(|(req, body, expect_continue)| {
+        if let Some(receiver) = expect_continue {
+            cynthia::runtime::spawn(send_continue(io, receiver)).detach();
+        }
+        (req, body)
+    }))
+}
+
+async fn send_continue<IO>(mut io: IO, receiver: Receiver<()>)
+where
+    IO: AsyncWrite + Unpin,
+{
+    if let Ok(()) = receiver.recv().await {
+        io.write_all(CONTINUE_RESPONSE).await.ok();
+    }
+}
+
+pub(crate) type Decoded<IO> = (Request, BodyReader<IO>, Option<Receiver<()>>);
+
+pub(crate) async fn decode_buffered<IO>(
+    io: IO,
+    buffered: Vec<u8>,
+    opts: &ServerOptions,
+    parsed_version: &mut Option<Version>,
+) -> crate::common::http_types::Result<Option<Decoded<IO>>>
+where
+    IO: AsyncRead + AsyncWrite + Clone + Send + Sync + Unpin + 'static,
+{
+    let mut reader = BufferReader::new(Rewind::new_buffered(io, buffered));
     let mut buf = Vec::new();
-    let mut headers = [httparse::EMPTY_HEADER; MAX_HEADERS];
+    let mut headers = vec![httparse::EMPTY_HEADER; opts.max_headers];
 
     let mut httparse_req = httparse::Request::new(&mut headers);
 
     loop {
-        let bytes_read = reader.read_until(LF, &mut buf).await?;
+        let remaining = opts.max_head_length.saturating_sub(buf.len());
+        if remaining == 0 {
+            if !buf.contains(&LF) {
+                crate::bail_status!(414, "Request line exceeds {} bytes", opts.max_head_length);
+            }
+            crate::bail_status!(431, "Head exceeds {} bytes", opts.max_head_length);
+        }
+
+        let bytes_read = (&mut reader)
+            .take(remaining as u64)
+            .read_until(LF, &mut buf)
+            .await?;
         if bytes_read == 0 {
             return Ok(None);
         }
 
-        ensure!(
-            buf.len() < MAX_HEAD_LENGTH,
-            "Head byte length should be less than 8kb"
-        );
-
         let idx = buf.len() - 1;
         if idx >= 3 && &buf[idx - 3..=idx] == b"\r\n\r\n" {
             break;
         }
     }
 
-    let status = httparse_req.parse(&buf)?;
+    let status = match httparse_req.parse(&buf) {
+        Ok(status) => status,
+        Err(httparse::Error::TooManyHeaders) => {
+            crate::bail_status!(431, "More than {} headers", opts.max_headers)
+        }
+        Err(e) => return Err(e.into()),
+    };
 
-    ensure!(!status.is_partial(), "Malformed HTTP head");
+    crate::ensure!(!status.is_partial(), "Malformed HTTP head");
+
+    if let (Some(max), Some(path)) = (opts.max_uri_length, httparse_req.path) {
+        crate::ensure_status!(path.len() <= max, 414, "URI exceeds {} bytes", max);
+    }
 
     let method = httparse_req.method;
     let method = method.ok_or_else(|| format_err!("No method found"))?;
@@ -59,23 +108,27 @@ where
     let version = httparse_req.version;
     let version = version.ok_or_else(|| format_err!("No version found"))?;
 
-    ensure_eq!(
-        version,
-        HTTP_1_1_VERSION,
-        "Unsupported HTTP version 1.{}",
-        version
-    );
+    let version = match version {
+        HTTP_1_0_VERSION => Version::Http1_0,
+        HTTP_1_1_VERSION => Version::Http1_1,
+        _ => crate::bail_status!(505, "Unsupported HTTP version 1.{}", version),
+    };
+    *parsed_version = Some(version);
 
-    let url = url_from_httparse_req(&httparse_req)?;
+    let url = url_from_httparse_req(&httparse_req, version)?;
 
     let mut req = Request::new(Method::from_str(method)?, url);
 
-    req.set_version(Some(crate::common::http_types::Version::Http1_1));
+    req.set_version(Some(version));
 
     for header in httparse_req.headers.iter() {
         req.append_header(header.name, std::str::from_utf8(header.value)?);
     }
 
+    if let Some(info) = &opts.connection_info {
+        info.apply(&mut req);
+    }
+
     let content_length = ContentLength::from_headers(&req)?;
     let transfer_encoding = req.header(TRANSFER_ENCODING);
 
@@ -85,44 +138,53 @@ where
         "Unexpected Content-Length header"
     );
 
+    if let (Some(max), Some(len)) = (opts.max_body_length, &content_length) {
+        crate::ensure_status!(len.len() <= max, 413, "Body exceeds {} bytes", max);
+    }
+
     let (body_read_sender, body_read_receiver) = cynthia::platform::channel::bounded(1);
 
-    if Some(CONTINUE_HEADER_VALUE) == req.header(EXPECT).map(|h| h.as_str()) {
-        cynthia::runtime::spawn(async move {
-            if let Ok(()) = body_read_receiver.recv().await {
-                io.write_all(CONTINUE_RESPONSE).await.ok();
-            };
-        })
-        .detach();
-    }
+    let expect_continue = if version != Version::Http1_0
+        && Some(CONTINUE_HEADER_VALUE) == req.header(EXPECT).map(|h| h.as_str())
+    {
+        Some(body_read_receiver)
+    } else {
+        None
+    };
 
     if transfer_encoding
         .map(|te| te.as_str().eq_ignore_ascii_case("chunked"))
         .unwrap_or(false)
     {
         let trailer_sender = req.send_trailers();
-        let reader = ChunkedDecoder::new(reader, trailer_sender);
-        let reader = Arc::new(Mutex::new(reader));
+        let reader = ChunkedDecoder::new(reader, trailer_sender).with_max_len(opts.max_body_length);
+        let reader = Shared::new(reader);
         let reader_clone = reader.clone();
-        let reader = ReadNotifier::new(reader, body_read_sender);
-        let reader = BufferReader::new(reader);
+        let reader = ReadNotifier::new(HandlerReader::new(reader), body_read_sender);
+        let reader = BufferReader::new(IoTimeout::new(reader, opts.read_timeout));
         req.set_body(Body::from_reader(reader, None));
-        return Ok(Some((req, BodyReader::Chunked(reader_clone))));
+        return Ok(Some((
+            req,
+            BodyReader::Chunked(reader_clone),
+            expect_continue,
+        )));
     } else if let Some(len) = content_length {
         let len = len.len();
-        let reader = Arc::new(Mutex::new(reader.take(len)));
+        let reader = Shared::new(reader.take(len));
+        let notifier = ReadNotifier::new(HandlerReader::new(reader.clone()), body_read_sender);
         req.set_body(Body::from_reader(
-            BufferReader::new(ReadNotifier::new(reader.clone(), body_read_sender)),
+            BufferReader::new(IoTimeout::new(notifier, opts.read_timeout)),
             Some(len as usize),
         ));
-        Ok(Some((req, BodyReader::Fixed(reader))))
+        Ok(Some((req, BodyReader::Fixed(reader), expect_continue)))
     } else {
-        Ok(Some((req, BodyReader::None)))
+        Ok(Some((req, BodyReader::None(reader), None)))
     }
 }
 
 fn url_from_httparse_req(
     req: &httparse::Request<'_, '_>,
+    version: Version,
 ) -> crate::common::http_types::Result<Url> {
     let path = req.path.ok_or_else(|| format_err!("No uri found"))?;
 
@@ -130,10 +192,13 @@ fn url_from_httparse_req(
         .headers
         .iter()
         .find(|x| x.name.eq_ignore_ascii_case("host"))
-        .ok_or_else(|| format_err!("Mandatory Host header missing"))?
-        .value;
+        .map(|x| x.value);
 
-    let host = std::str::from_utf8(host)?;
+    let host = match (host, version) {
+        (Some(host), _) => std::str::from_utf8(host)?,
+        (None, Version::Http1_0) => "localhost",
+        (None, _) => return Err(format_err!("Mandatory Host header missing")),
+    };
 
     if path.starts_with("http://") || path.starts_with("https://") {
         Ok(Url::parse(path)?)
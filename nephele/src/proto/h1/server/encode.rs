@@ -4,9 +4,12 @@ use std::io::Write;
 use std::pin::Pin;
 use std::time::SystemTime;
 
-use crate::common::http_types::headers::{CONTENT_LENGTH, DATE, TRANSFER_ENCODING};
-use crate::common::http_types::{Method, Response};
+use crate::common::http_types::headers::{
+    CONNECTION, CONTENT_LENGTH, DATE, TRAILER, TRANSFER_ENCODING,
+};
+use crate::common::http_types::{Method, Response, StatusCode, Version};
 use crate::proto::h1::body_encoder::BodyEncoder;
+use crate::proto::h1::chunked::PendingTrailers;
 use crate::proto::h1::date::fmt_http_date;
 use crate::proto::h1::EncoderState;
 use crate::read_to_end;
@@ -16,6 +19,8 @@ pub struct Encoder {
     response: Response,
     state: EncoderState,
     method: Method,
+    version: Version,
+    trailers: Option<PendingTrailers>,
 }
 
 impl AsyncRead for Encoder {
@@ -31,10 +36,15 @@ impl AsyncRead for Encoder {
                 EncoderState::Head(ref mut cursor) => {
                     read_to_end!(Pin::new(cursor).poll_read(cx, buf));
 
-                    if self.method == Method::Head {
+                    if self.method == Method::Head || self.bodiless() {
                         EncoderState::End
+                    } else if self.version == Version::Http1_0 {
+                        let body = self.response.take_body();
+                        EncoderState::Body(BodyEncoder::close_delimited(body))
                     } else {
-                        EncoderState::Body(BodyEncoder::new(self.response.take_body()))
+                        let body = self.response.take_body();
+                        let trailers = self.trailers.take();
+                        EncoderState::Body(BodyEncoder::with_trailers(body, trailers))
                     }
                 }
 
@@ -51,16 +61,35 @@ impl AsyncRead for Encoder {
 
 impl Encoder {
     pub fn new(response: Response, method: Method) -> Self {
+        Self::with_version(response, method, Version::Http1_1)
+    }
+
+    pub fn with_version(response: Response, method: Method, version: Version) -> Self {
         Self {
             method,
             response,
+            version,
+            trailers: None,
             state: EncoderState::Start,
         }
     }
 
     fn finalize_headers(&mut self) {
-        if let Some(len) = self.response.len() {
+        let bodiless = self.bodiless();
+        let trailers =
+            !bodiless && self.version != Version::Http1_0 && self.response.has_trailers();
+        if trailers {
+            self.response.remove_header(CONTENT_LENGTH);
+            self.response.insert_header(TRANSFER_ENCODING, "chunked");
+            self.declare_trailers();
+        } else if let (false, Some(len)) = (bodiless, self.response.len()) {
             self.response.insert_header(CONTENT_LENGTH, len.to_string());
+        } else if bodiless {
+            self.response.remove_header(CONTENT_LENGTH);
+            self.response.remove_header(TRANSFER_ENCODING);
+        } else if self.version == Version::Http1_0 {
+            self.response.remove_header(TRANSFER_ENCODING);
+            self.response.insert_header(CONNECTION, "close");
         } else {
             self.response.insert_header(TRANSFER_ENCODING, "chunked");
         }
@@ -71,6 +100,22 @@ impl Encoder {
         }
     }
 
+    fn bodiless(&self) -> bool {
+        let status = self.response.status();
+        status.is_informational()
+            || status == StatusCode::NoContent
+            || status == StatusCode::NotModified
+    }
+
+    fn declare_trailers(&mut self) {
+        let pending = PendingTrailers::new(self.response.recv_trailers());
+        match pending.declared(self.response.header(TRAILER)) {
+            Some(names) => self.response.insert_header(TRAILER, names),
+            None => self.response.remove_header(TRAILER),
+        };
+        self.trailers = Some(pending);
+    }
+
     fn compute_head(&mut self) -> swap::Result<Cursor<Vec<u8>>> {
         let mut head = Vec::with_capacity(128);
         let reason = self.response.status().canonical_reason();
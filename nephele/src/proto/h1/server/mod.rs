@@ -1,27 +1,180 @@
-use cynthia::future::swap::{self, AsyncRead, AsyncWrite};
-use cynthia::future::{timeout, Future, TimeoutError};
-use std::{marker::PhantomData, time::Duration};
+use cynthia::future::future::{or, pending};
+use cynthia::future::prelude::*;
+use cynthia::future::swap::{self, AsyncRead, AsyncWrite, ErrorKind};
+use cynthia::future::{timeout, Future, Stream, TimeoutError};
+use cynthia::platform::channel::{Receiver, TryRecvError};
+use cynthia::runtime::task::Poll;
+use std::fmt::{self, Debug};
+use std::{io, marker::PhantomData, mem, pin::Pin, sync::Arc, time::Duration};
 
-use crate::common::http_types::headers::{CONNECTION, UPGRADE};
+use crate::common::http_types::headers::{CONNECTION, HTTP2_SETTINGS, UPGRADE};
 use crate::common::http_types::upgrade::Connection;
-use crate::common::http_types::{Request, Response, StatusCode};
+use crate::common::http_types::{Body, Error, Method, Request, Response, StatusCode, Version};
+use crate::proto::h1::io_timeout::IoTimeout;
+use crate::proto::h1::rewind::Rewind;
+use crate::proto::h1::{has_connection_token, MAX_HEADERS, MAX_HEAD_LENGTH};
+use crate::proto::h2::h2c::{self, Settings};
+use crate::proto::{ConnectionInfo, Shutdown};
 
 mod body_reader;
 mod decode;
 mod encode;
 
+const READ_BUFFER_SIZE: usize = 8 * 1024;
+const MAX_H2C_BODY_LENGTH: usize = 64 * 1024;
+
+use body_reader::BodyReader;
 pub use decode::decode;
+use decode::{decode_buffered, CONTINUE_RESPONSE};
 pub use encode::Encoder;
 
-#[derive(Debug, Clone)]
+type ErrorRenderer = Arc<dyn Fn(&Error) -> Response + Send + Sync + 'static>;
+
+#[derive(Clone)]
 pub struct ServerOptions {
-    headers_timeout: Option<Duration>,
+    pub(crate) headers_timeout: Option<Duration>,
+    keep_alive_timeout: Option<Duration>,
+    pub(crate) read_timeout: Option<Duration>,
+    pub(crate) write_timeout: Option<Duration>,
+    max_head_length: usize,
+    max_headers: usize,
+    max_uri_length: Option<usize>,
+    pub(crate) max_body_length: Option<u64>,
+    error_renderer: Option<ErrorRenderer>,
+    pub(crate) shutdown: Option<Shutdown>,
+    pub(crate) shutdown_timeout: Option<Duration>,
+    pub(crate) connection_info: Option<ConnectionInfo>,
+    pub(crate) enable_connect_protocol: bool,
+}
+
+impl ServerOptions {
+    pub fn headers_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.headers_timeout = timeout;
+        self
+    }
+
+    pub fn keep_alive_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.keep_alive_timeout = timeout;
+        self
+    }
+
+    pub fn read_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+
+    pub fn write_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.write_timeout = timeout;
+        self
+    }
+
+    pub fn max_head_length(mut self, max: usize) -> Self {
+        self.max_head_length = max;
+        self
+    }
+
+    pub fn max_headers(mut self, max: usize) -> Self {
+        self.max_headers = max;
+        self
+    }
+
+    pub fn max_uri_length(mut self, max: Option<usize>) -> Self {
+        self.max_uri_length = max;
+        self
+    }
+
+    pub fn max_body_length(mut self, max: Option<u64>) -> Self {
+        self.max_body_length = max;
+        self
+    }
+
+    pub fn error_renderer<F>(mut self, renderer: F) -> Self
+    where
+        F: Fn(&Error) -> Response + Send + Sync + 'static,
+    {
+        self.error_renderer = Some(Arc::new(renderer));
+        self
+    }
+
+    pub fn shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = Some(shutdown);
+        self
+    }
+
+    pub fn shutdown_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    pub fn connection_info(mut self, info: ConnectionInfo) -> Self {
+        self.connection_info = Some(info);
+        self
+    }
+
+    pub fn enable_connect_protocol(mut self, enabled: bool) -> Self {
+        self.enable_connect_protocol = enabled;
+        self
+    }
+
+    pub(crate) fn is_shutdown(&self) -> bool {
+        self.shutdown.as_ref().map_or(false, |s| s.is_shutdown())
+    }
+
+    pub(crate) async fn shutdown_signaled(&self) {
+        match &self.shutdown {
+            Some(shutdown) => shutdown.signaled().await,
+            None => pending().await,
+        }
+    }
+
+    pub(crate) fn render_error(&self, error: &Error) -> Response {
+        match &self.error_renderer {
+            Some(renderer) => renderer(error),
+            None => Response::new(error.status()),
+        }
+    }
+}
+
+impl Debug for ServerOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let error_renderer = self
+            .error_renderer
+            .as_ref()
+            .map(|_| "Fn(&Error) -> Response");
+        f.debug_struct("ServerOptions")
+            .field("headers_timeout", &self.headers_timeout)
+            .field("keep_alive_timeout", &self.keep_alive_timeout)
+            .field("read_timeout", &self.read_timeout)
+            .field("write_timeout", &self.write_timeout)
+            .field("max_head_length", &self.max_head_length)
+            .field("max_headers", &self.max_headers)
+            .field("max_uri_length", &self.max_uri_length)
+            .field("max_body_length", &self.max_body_length)
+            .field("error_renderer", &error_renderer)
+            .field("shutdown", &self.shutdown)
+            .field("shutdown_timeout", &self.shutdown_timeout)
+            .field("connection_info", &self.connection_info)
+            .field("enable_connect_protocol", &self.enable_connect_protocol)
+            .finish()
+    }
 }
 
 impl Default for ServerOptions {
     fn default() -> Self {
         Self {
             headers_timeout: Some(Duration::from_secs(60)),
+            keep_alive_timeout: Some(Duration::from_secs(60)),
+            read_timeout: None,
+            write_timeout: None,
+            max_head_length: MAX_HEAD_LENGTH,
+            max_headers: MAX_HEADERS,
+            max_uri_length: None,
+            max_body_length: None,
+            error_renderer: None,
+            shutdown: None,
+            shutdown_timeout: Some(Duration::from_secs(30)),
+            connection_info: None,
+            enable_connect_protocol: false,
         }
     }
 }
@@ -53,6 +206,10 @@ pub struct Server<RW, F, Fut> {
     io: RW,
     endpoint: F,
     opts: ServerOptions,
+    buffered: Vec<u8>,
+    idle: bool,
+    h2c: bool,
+    upgraded: Option<(Request, Settings)>,
     _phantom: PhantomData<Fut>,
 }
 
@@ -73,6 +230,10 @@ where
             io,
             endpoint,
             opts: Default::default(),
+            buffered: Vec::new(),
+            idle: false,
+            h2c: false,
+            upgraded: None,
             _phantom: PhantomData,
         }
     }
@@ -82,6 +243,21 @@ where
         self
     }
 
+    pub(crate) fn with_buffered(mut self, buffered: Vec<u8>) -> Self {
+        self.buffered = buffered;
+        self
+    }
+
+    pub(crate) fn with_h2c(mut self) -> Self {
+        self.h2c = true;
+        self
+    }
+
+    pub(crate) fn take_h2c(&mut self) -> Option<(Request, Settings, Vec<u8>)> {
+        let (req, settings) = self.upgraded.take()?;
+        Some((req, settings, mem::take(&mut self.buffered)))
+    }
+
     pub async fn accept(&mut self) -> crate::common::http_types::Result<()> {
         while ConnectionStatus::KeepAlive == self.accept_one().await? {}
         Ok(())
@@ -93,40 +269,114 @@ where
         F: Fn(Request) -> Fut,
         Fut: Future<Output = crate::common::http_types::Result<Response>>,
     {
-        let fut = decode(self.io.clone());
+        if self.idle && self.buffered.is_empty() {
+            let mut buf = vec![0; READ_BUFFER_SIZE];
+            let read = self.io.read(&mut buf);
+            let shutdown = self.opts.shutdown_signaled();
+            let fut = or(async { Some(read.await) }, async {
+                shutdown.await;
+                None
+            });
+
+            let bytes_read = if let Some(timeout_duration) = self.opts.keep_alive_timeout {
+                match timeout(timeout_duration, fut).await {
+                    Ok(bytes_read) => bytes_read,
+                    Err(TimeoutError { .. }) => return Ok(ConnectionStatus::Close), /* timeout */
+                }
+            } else {
+                fut.await
+            };
 
-        let (req, mut body) = if let Some(timeout_duration) = self.opts.headers_timeout {
+            let bytes_read = match bytes_read {
+                Some(bytes_read) => bytes_read?,
+                None => return Ok(ConnectionStatus::Close), /* shutdown */
+            };
+
+            if bytes_read == 0 {
+                return Ok(ConnectionStatus::Close); /* EOF */
+            }
+
+            buf.truncate(bytes_read);
+            self.buffered = buf;
+        }
+        self.idle = false;
+
+        let mut parsed_version = None;
+        let buffered = mem::take(&mut self.buffered);
+        let fut = decode_buffered(self.io.clone(), buffered, &self.opts, &mut parsed_version);
+
+        let decoded = if let Some(timeout_duration) = self.opts.headers_timeout {
             match timeout(timeout_duration, fut).await {
-                Ok(Ok(Some(r))) => r,
-                Ok(Ok(None)) | Err(TimeoutError { .. }) => return Ok(ConnectionStatus::Close), /* EOF or timeout */
-                Ok(Err(e)) => return Err(e),
+                Ok(decoded) => decoded,
+                Err(TimeoutError { .. }) => return Ok(ConnectionStatus::Close), /* timeout */
             }
         } else {
-            match fut.await? {
-                Some(r) => r,
-                None => return Ok(ConnectionStatus::Close), /* EOF */
+            fut.await
+        };
+
+        let (req, mut body, expect_continue) = match decoded {
+            Ok(Some(r)) => r,
+            Ok(None) => return Ok(ConnectionStatus::Close), /* EOF */
+            Err(e) if e.downcast_ref::<io::Error>().is_some() => return Err(e),
+            Err(mut e) => {
+                if e.status() == StatusCode::InternalServerError {
+                    e.set_status(StatusCode::BadRequest);
+                }
+                let version = parsed_version.unwrap_or(Version::Http1_0);
+                return self.reject(e, Method::Get, version).await;
             }
         };
 
-        let has_upgrade_header = req.header(UPGRADE).is_some();
-        let connection_header_as_str = req
-            .header(CONNECTION)
-            .map(|connection| connection.as_str())
-            .unwrap_or("");
+        let version = req.version().unwrap_or(Version::Http1_1);
 
-        let connection_header_is_upgrade = connection_header_as_str.eq_ignore_ascii_case("upgrade");
-        let mut close_connection = connection_header_as_str.eq_ignore_ascii_case("close");
+        let has_upgrade_header = req.header(UPGRADE).is_some();
+        let connection_header_is_upgrade = has_connection_token(&req, "upgrade");
+        let mut close_connection = match version {
+            Version::Http1_0 => !has_connection_token(&req, "keep-alive"),
+            _ => has_connection_token(&req, "close"),
+        };
 
-        let upgrade_requested = has_upgrade_header && connection_header_is_upgrade;
+        let upgrade_requested =
+            has_upgrade_header && connection_header_is_upgrade && version != Version::Http1_0;
 
         let method = req.method();
 
-        let mut res = (self.endpoint)(req).await?;
+        if self.h2c && upgrade_requested {
+            let bounded = req.len().map_or(true, |len| len <= MAX_H2C_BODY_LENGTH);
+            if let (true, Some(settings)) = (bounded, h2c_settings(&req)) {
+                return self.upgrade_h2c(req, body, settings).await;
+            }
+        }
+
+        let (res, continued) = match self.respond(req, expect_continue).await? {
+            Some(responded) => responded,
+            None => return Ok(ConnectionStatus::Close),
+        };
+
+        if body.limit_exceeded() {
+            let max = self.opts.max_body_length.unwrap_or_default();
+            let e = crate::format_err_status!(413, "Body exceeds {} bytes", max);
+            return self.reject(e, method, version).await;
+        }
+
+        let mut res = match res {
+            Ok(res) => res,
+            Err(e) => return self.reject(e, method, version).await,
+        };
+
+        close_connection |= has_connection_token(&res, "close");
+
+        if !continued || self.opts.is_shutdown() {
+            res.insert_header(CONNECTION, "close");
+            close_connection = true;
+        }
 
-        close_connection |= res
-            .header(CONNECTION)
-            .map(|c| c.as_str().eq_ignore_ascii_case("close"))
-            .unwrap_or(false);
+        if version == Version::Http1_0 {
+            close_connection |= res.len().is_none();
+            if !close_connection {
+                res.insert_header(CONNECTION, "keep-alive");
+            }
+        }
 
         let upgrade_provided = res.status() == StatusCode::SwitchingProtocols && res.has_upgrade();
 
@@ -136,19 +386,177 @@ where
             None
         };
 
-        let mut encoder = Encoder::new(res, method);
+        let mut encoder = Encoder::with_version(res, method, version);
 
-        let _bytes_written = swap::copy(&mut encoder, &mut self.io).await?;
+        let mut writer = IoTimeout::new(&mut self.io, self.opts.write_timeout);
+        swap::copy(&mut encoder, &mut writer).await?;
 
-        let _body_bytes_discarded = swap::copy(&mut body, &mut swap::sink()).await?;
+        body.detach();
+
+        if !continued {
+            return Ok(ConnectionStatus::Close);
+        }
+
+        let mut reader = IoTimeout::new(&mut body, self.opts.read_timeout);
+        let drained = swap::copy(&mut reader, &mut swap::sink()).await;
+
+        if body.limit_exceeded() {
+            return Ok(ConnectionStatus::Close);
+        }
+
+        if let Err(e) = &drained {
+            if e.kind() == ErrorKind::TimedOut {
+                tracing::debug!("timed out draining request body: {}", e);
+                return Ok(ConnectionStatus::Close);
+            }
+        }
+
+        let _body_bytes_discarded = drained?;
+
+        self.buffered = body.buffered();
 
         if let Some(upgrade_sender) = upgrade_sender {
-            upgrade_sender.send(Connection::new(self.io.clone())).await;
+            let io = Rewind::new_buffered(self.io.clone(), mem::take(&mut self.buffered));
+            upgrade_sender.send(Connection::new(io)).await;
             return Ok(ConnectionStatus::Close);
         } else if close_connection {
             Ok(ConnectionStatus::Close)
         } else {
+            self.idle = true;
             Ok(ConnectionStatus::KeepAlive)
         }
     }
+
+    async fn respond(
+        &mut self,
+        req: Request,
+        mut expect_continue: Option<Receiver<()>>,
+    ) -> crate::common::http_types::Result<
+        Option<(crate::common::http_types::Result<Response>, bool)>,
+    > {
+        let mut endpoint = Box::pin((self.endpoint)(req));
+
+        let res = loop {
+            let polled = futures_util::future::poll_fn(|cx| {
+                if let Some(receiver) = expect_continue.as_mut() {
+                    if let Poll::Ready(read) = Pin::new(receiver).poll_next(cx) {
+                        return Poll::Ready(Err(read.is_some()));
+                    }
+                }
+                endpoint.as_mut().poll(cx).map(Ok)
+            })
+            .await;
+
+            match polled {
+                Ok(res) => break res,
+                Err(true) => {
+                    expect_continue = None;
+                    if !self.send_continue().await? {
+                        return Ok(None);
+                    }
+                }
+                Err(false) => return Ok(Some((endpoint.await, false))),
+            }
+        };
+
+        let continued = match expect_continue.map(|receiver| receiver.try_recv()) {
+            Some(Ok(())) => self.send_continue().await?,
+            Some(Err(TryRecvError::Empty)) | Some(Err(TryRecvError::Closed)) => false,
+            None => true,
+        };
+
+        Ok(Some((res, continued)))
+    }
+
+    async fn send_continue(&mut self) -> crate::common::http_types::Result<bool> {
+        let mut writer = IoTimeout::new(&mut self.io, self.opts.write_timeout);
+        match writer.write_all(CONTINUE_RESPONSE).await {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::TimedOut => {
+                tracing::debug!("timed out sending 100 Continue: {}", e);
+                Ok(false)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn upgrade_h2c(
+        &mut self,
+        mut req: Request,
+        mut body: BodyReader<RW>,
+        settings: Settings,
+    ) -> crate::common::http_types::Result<ConnectionStatus> {
+        let method = req.method();
+        let version = req.version().unwrap_or(Version::Http1_1);
+
+        let mut bytes = Vec::new();
+        let bounded = (&mut body).take(MAX_H2C_BODY_LENGTH as u64 + 1);
+        let mut reader = IoTimeout::new(bounded, self.opts.read_timeout);
+        reader.read_to_end(&mut bytes).await?;
+
+        if body.limit_exceeded() {
+            let max = self.opts.max_body_length.unwrap_or_default();
+            let e = crate::format_err_status!(413, "Body exceeds {} bytes", max);
+            return self.reject(e, method, version).await;
+        }
+
+        if bytes.len() > MAX_H2C_BODY_LENGTH {
+            let e = crate::format_err_status!(
+                413,
+                "h2c upgrade body exceeds {} bytes",
+                MAX_H2C_BODY_LENGTH
+            );
+            return self.reject(e, method, version).await;
+        }
+
+        req.set_body(Body::from_bytes(bytes));
+
+        let mut res = Response::new(StatusCode::SwitchingProtocols);
+        res.insert_header(CONNECTION, "upgrade");
+        res.insert_header(UPGRADE, "h2c");
+
+        let mut encoder = Encoder::with_version(res, method, version);
+        let mut writer = IoTimeout::new(&mut self.io, self.opts.write_timeout);
+        swap::copy(&mut encoder, &mut writer).await?;
+
+        self.buffered = body.buffered();
+        self.upgraded = Some((req, settings));
+        Ok(ConnectionStatus::Close)
+    }
+
+    async fn reject(
+        &mut self,
+        error: Error,
+        method: Method,
+        version: Version,
+    ) -> crate::common::http_types::Result<ConnectionStatus> {
+        let mut res = self.opts.render_error(&error);
+        res.insert_header(CONNECTION, "close");
+
+        let mut encoder = Encoder::with_version(res, method, version);
+        let mut writer = IoTimeout::new(&mut self.io, self.opts.write_timeout);
+        swap::copy(&mut encoder, &mut writer).await?;
+
+        Ok(ConnectionStatus::Close)
+    }
+}
+
+fn h2c_settings(req: &Request) -> Option<Settings> {
+    let upgrade = req.header(UPGRADE)?;
+    let is_h2c = upgrade.iter().any(|value| {
+        value
+            .as_str()
+            .split(',')
+            .any(|t| t.trim().eq_ignore_ascii_case("h2c"))
+    });
+    if !is_h2c || !has_connection_token(req, "http2-settings") {
+        return None;
+    }
+
+    let values = req.header(HTTP2_SETTINGS)?;
+    if values.iter().count() != 1 {
+        return None;
+    }
+
+    h2c::decode_settings(values.last().as_str()).ok()
 }
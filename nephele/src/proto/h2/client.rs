@@ -10,9 +10,10 @@ use std::usize;
 use tracing_futures::Instrument;
 
 use crate::proto::h2::codec::{Codec, RecvError, SendError, UserError};
+use crate::proto::h2::ext::Protocol;
 use crate::proto::h2::frame::{Headers, Pseudo, Reason, Settings, StreamId};
 use crate::proto::h2::proto;
-use crate::proto::h2::{FlowControl, PingPong, RecvStream, SendStream};
+use crate::proto::h2::{Extensions, FlowControl, PingPong, RecvStream, SendStream};
 
 pub struct SendRequest<B: Buf> {
     inner: proto::Streams<B, Peer>,
@@ -59,6 +60,10 @@ pub struct Builder {
     reset_stream_duration: Duration,
     initial_max_send_streams: usize,
     initial_target_connection_window_size: Option<u32>,
+    adaptive_window: bool,
+    keep_alive_interval: Option<Duration>,
+    keep_alive_timeout: Duration,
+    keep_alive_while_idle: bool,
     reset_stream_max: usize,
     settings: Settings,
     stream_id: StreamId,
@@ -81,6 +86,10 @@ where
         ReadySendRequest { inner: Some(self) }
     }
 
+    pub fn is_extended_connect_protocol_enabled(&self) -> bool {
+        self.inner.is_extended_connect_protocol_enabled()
+    }
+
     pub fn send_request(
         &mut self,
         request: Request<()>,
@@ -106,6 +115,21 @@ where
     }
 }
 
+impl SendRequest<Bytes> {
+    pub(crate) fn send_upgrade(
+        &mut self,
+        head: bool,
+    ) -> Result<ResponseFuture, crate::proto::h2::Error> {
+        self.inner
+            .send_upgrade(head)
+            .map_err(Into::into)
+            .map(|stream| ResponseFuture {
+                inner: stream.clone_to_opaque(),
+                push_promise_consumed: false,
+            })
+    }
+}
+
 impl<B> fmt::Debug for SendRequest<B>
 where
     B: Buf,
@@ -165,6 +189,10 @@ impl Builder {
             reset_stream_duration: Duration::from_secs(proto::DEFAULT_RESET_STREAM_SECS),
             reset_stream_max: proto::DEFAULT_RESET_STREAM_MAX,
             initial_target_connection_window_size: None,
+            adaptive_window: false,
+            keep_alive_interval: None,
+            keep_alive_timeout: Duration::from_secs(proto::DEFAULT_KEEP_ALIVE_TIMEOUT_SECS),
+            keep_alive_while_idle: false,
             initial_max_send_streams: usize::MAX,
             settings: Default::default(),
             stream_id: 1.into(),
@@ -181,6 +209,26 @@ impl Builder {
         self
     }
 
+    pub fn adaptive_window(&mut self, enabled: bool) -> &mut Self {
+        self.adaptive_window = enabled;
+        self
+    }
+
+    pub fn keep_alive_interval(&mut self, interval: Duration) -> &mut Self {
+        self.keep_alive_interval = Some(interval);
+        self
+    }
+
+    pub fn keep_alive_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.keep_alive_timeout = timeout;
+        self
+    }
+
+    pub fn keep_alive_while_idle(&mut self, enabled: bool) -> &mut Self {
+        self.keep_alive_while_idle = enabled;
+        self
+    }
+
     pub fn max_frame_size(&mut self, max: u32) -> &mut Self {
         self.settings.set_max_frame_size(Some(max));
         self
@@ -216,6 +264,16 @@ impl Builder {
         self
     }
 
+    pub fn enable_connect_protocol(&mut self) -> &mut Self {
+        self.settings.set_enable_connect_protocol(Some(1));
+        self
+    }
+
+    pub fn setting(&mut self, id: u16, value: u32) -> &mut Self {
+        self.settings.set_extension(id, Some(value));
+        self
+    }
+
     #[cfg(feature = "unstable")]
     pub fn initial_stream_id(&mut self, stream_id: u32) -> &mut Self {
         self.stream_id = stream_id.into();
@@ -226,6 +284,10 @@ impl Builder {
         self
     }
 
+    pub(crate) fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
     pub fn handshake<T, B>(
         &self,
         io: T,
@@ -296,6 +358,10 @@ where
                 initial_max_send_streams: builder.initial_max_send_streams,
                 reset_stream_duration: builder.reset_stream_duration,
                 reset_stream_max: builder.reset_stream_max,
+                adaptive_window: builder.adaptive_window,
+                keep_alive_interval: builder.keep_alive_interval,
+                keep_alive_timeout: builder.keep_alive_timeout,
+                keep_alive_while_idle: builder.keep_alive_while_idle,
                 settings: builder.settings.clone(),
             },
         );
@@ -323,6 +389,14 @@ where
         Ok(())
     }
 
+    pub fn bdp_estimate(&self) -> Option<u32> {
+        self.inner.bdp_estimate()
+    }
+
+    pub fn extensions(&mut self) -> Option<Extensions> {
+        self.inner.take_user_extensions().map(Extensions::new)
+    }
+
     pub fn ping_pong(&mut self) -> Option<PingPong> {
         self.inner.take_user_pings().map(PingPong::new)
     }
@@ -455,14 +529,21 @@ impl Peer {
                 uri,
                 headers,
                 version,
+                mut extensions,
                 ..
             },
             _,
         ) = request.into_parts();
 
         let is_connect = method == Method::CONNECT;
+        let protocol = extensions.remove::<Protocol>();
+
+        if protocol.is_some() && !is_connect {
+            tracing::debug!("convert_send_message: :protocol without CONNECT");
+            return Err(UserError::MalformedHeaders.into());
+        }
 
-        let mut pseudo = Pseudo::request(method, uri);
+        let mut pseudo = Pseudo::request(method, uri, protocol);
 
         if pseudo.scheme.is_none() {
             if pseudo.authority.is_none() {
@@ -31,6 +31,7 @@ pub enum UserError {
     SendPingWhilePending,
     SendSettingsWhilePending,
     PeerDisabledServerPush,
+    ExtendedConnectProtocolDisabled,
 }
 
 impl From<io::Error> for RecvError {
@@ -98,6 +99,7 @@ impl fmt::Display for UserError {
             PollResetAfterSendResponse => "poll_reset after send_response is illegal",
             SendPingWhilePending => "send_ping before received previous pong",
             SendSettingsWhilePending => "sending SETTINGS before received previous ACK",
+            ExtendedConnectProtocolDisabled => "extended CONNECT protocol not enabled by peer",
             PeerDisabledServerPush => "sending PUSH_PROMISE to peer who disabled server push",
         })
     }
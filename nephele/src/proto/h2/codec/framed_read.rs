@@ -195,6 +195,17 @@ impl<T> FramedRead<T> {
                     }
                 }
             }
+            Kind::PriorityUpdate => {
+                let res = frame::PriorityUpdate::load(head, &bytes[frame::HEADER_LEN..]);
+                res.map_err(|e| {
+                    proto_err!(conn: "failed to load PRIORITY_UPDATE frame; err={:?}", e);
+                    match e {
+                        frame::Error::BadFrameSize => Connection(Reason::FRAME_SIZE_ERROR),
+                        _ => Connection(Reason::PROTOCOL_ERROR),
+                    }
+                })?
+                .into()
+            }
             Kind::Continuation => {
                 let is_end_headers = (head.flag() & 0x4) == 0x4;
 
@@ -253,7 +264,9 @@ impl<T> FramedRead<T> {
                 }
             }
             Kind::Unknown => {
-                return Ok(None);
+                let raw_kind = bytes[3];
+                let payload = bytes.split_off(frame::HEADER_LEN).freeze();
+                frame::ExtensionFrame::load(head, raw_kind, payload).into()
             }
         };
 
@@ -127,6 +127,11 @@ where
                 tracing::trace!(rem = self.buf.remaining(), "encoded window_update");
             }
 
+            Frame::PriorityUpdate(v) => {
+                v.encode(self.buf.get_mut());
+                tracing::trace!(rem = self.buf.remaining(), "encoded priority_update");
+            }
+
             Frame::Priority(_) => {
                 unimplemented!();
             }
@@ -134,6 +139,14 @@ where
                 v.encode(self.buf.get_mut());
                 tracing::trace!(rem = self.buf.remaining(), "encoded reset");
             }
+            Frame::Extension(v) => {
+                if v.payload().len() > self.max_frame_size() {
+                    return Err(PayloadTooBig);
+                }
+
+                v.encode(self.buf.get_mut());
+                tracing::trace!(rem = self.buf.remaining(), "encoded extension frame");
+            }
         }
 
         Ok(())
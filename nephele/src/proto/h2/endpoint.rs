@@ -0,0 +1,664 @@
+use bytes::{Buf, Bytes};
+use cynthia::future::future::or;
+use cynthia::future::prelude::*;
+use cynthia::future::swap::{self, AsyncRead, AsyncWrite, BufferReader};
+use cynthia::future::timeout;
+use std::cmp;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use crate::common::http_types::headers::{CONTENT_LENGTH, HOST, PRIORITY};
+use crate::common::http_types::trailers::{self, Trailers};
+use crate::common::http_types::upgrade::Connection;
+use crate::common::http_types::{Body, Method, Request, Response, StatusCode, Url, Version};
+use crate::format_err;
+use crate::proto::h1::io_timeout::IoTimeout;
+use crate::proto::h1::ServerOptions;
+use crate::proto::h2::client::SendRequest;
+use crate::proto::h2::ext::{Priority, Protocol};
+use crate::proto::h2::h2c::Settings;
+use crate::proto::h2::server::{self, SendResponse};
+use crate::proto::h2::{RecvStream, SendStream};
+use crate::proto::ConnectionInfo;
+
+const MAX_DATA_CHUNK: usize = 16 * 1024;
+
+const CONNECTION_HEADERS: [&str; 5] = [
+    "connection",
+    "keep-alive",
+    "proxy-connection",
+    "transfer-encoding",
+    "upgrade",
+];
+
+const UPGRADE_HEADERS: [&str; 3] = ["host", "content-length", "http2-settings"];
+
+pub async fn accept<RW, F, Fut>(io: RW, endpoint: F) -> crate::common::http_types::Result<()>
+where
+    RW: AsyncRead + AsyncWrite + Unpin,
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+{
+    accept_with_opts(io, endpoint, ServerOptions::default()).await
+}
+
+pub async fn accept_with_opts<RW, F, Fut>(
+    io: RW,
+    endpoint: F,
+    opts: ServerOptions,
+) -> crate::common::http_types::Result<()>
+where
+    RW: AsyncRead + AsyncWrite + Unpin,
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+{
+    let connection = builder(&opts).handshake(io).await?;
+    serve_connection(connection, Arc::new(endpoint), None, opts).await
+}
+
+pub(crate) async fn accept_h2c<RW, F, Fut>(
+    io: RW,
+    endpoint: Arc<F>,
+    settings: &Settings,
+    mut req: Request,
+    opts: ServerOptions,
+) -> crate::common::http_types::Result<()>
+where
+    RW: AsyncRead + AsyncWrite + Unpin,
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+{
+    let mut head = http::Request::builder()
+        .method(req.method().as_ref())
+        .uri(req.url().as_str());
+    for (name, values) in req.iter() {
+        if CONNECTION_HEADERS.contains(&name.as_str()) || UPGRADE_HEADERS.contains(&name.as_str()) {
+            continue;
+        }
+        for value in values.iter() {
+            head = head.header(name.as_str(), value.as_str());
+        }
+    }
+    let head = head.body(())?;
+
+    req.set_version(Some(Version::Http2_0));
+
+    let connection = server::handshake_h2c(&builder(&opts), io, settings, head).await?;
+    serve_connection(connection, endpoint, Some(req), opts).await
+}
+
+fn builder(opts: &ServerOptions) -> server::Builder {
+    let mut builder = server::Builder::new();
+    if opts.enable_connect_protocol {
+        builder.enable_connect_protocol();
+    }
+    builder
+}
+
+async fn serve_connection<RW, F, Fut>(
+    mut connection: server::Connection<RW, Bytes>,
+    endpoint: Arc<F>,
+    mut upgraded: Option<Request>,
+    mut opts: ServerOptions,
+) -> crate::common::http_types::Result<()>
+where
+    RW: AsyncRead + AsyncWrite + Unpin,
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+{
+    let mut shutdown = opts.shutdown.take();
+    let info = opts.connection_info.take();
+    let opts = Arc::new(opts);
+
+    loop {
+        let accepted = match &shutdown {
+            Some(signal) => {
+                let accept = connection.accept();
+                or(async { Some(accept.await) }, async {
+                    signal.signaled().await;
+                    None
+                })
+                .await
+            }
+            None => Some(connection.accept().await),
+        };
+
+        let result = match accepted {
+            Some(Some(result)) => result,
+            Some(None) => break,
+            None => {
+                connection.graceful_shutdown();
+                shutdown = None;
+                continue;
+            }
+        };
+
+        let (req, respond) = result?;
+        let limit_exceeded = Arc::new(AtomicBool::new(false));
+        let (req, tunnel) = match upgraded.take() {
+            Some(upgraded) => (Ok(upgraded), None),
+            None if req.extensions().get::<Protocol>().is_some() => {
+                let (parts, body) = req.into_parts();
+                (request_head_from_h2(parts, info.as_ref()), Some(body))
+            }
+            None => (
+                request_from_h2(req, info.as_ref(), &opts, limit_exceeded.clone()),
+                None,
+            ),
+        };
+
+        let endpoint = endpoint.clone();
+        let opts = opts.clone();
+        cynthia::runtime::spawn(async move {
+            let responded =
+                respond_with(req, tunnel, respond, &*endpoint, &opts, &limit_exceeded).await;
+            if let Err(e) = responded {
+                tracing::debug!("h2 stream error: {}", e);
+            }
+        })
+        .detach();
+    }
+
+    Ok(())
+}
+
+async fn respond_with<F, Fut>(
+    req: crate::common::http_types::Result<Request>,
+    tunnel: Option<RecvStream>,
+    mut respond: SendResponse<Bytes>,
+    endpoint: &F,
+    opts: &ServerOptions,
+    limit_exceeded: &AtomicBool,
+) -> crate::common::http_types::Result<()>
+where
+    F: Fn(Request) -> Fut,
+    Fut: Future<Output = crate::common::http_types::Result<Response>>,
+{
+    let res = match req {
+        Ok(req) => endpoint(req).await,
+        Err(mut e) => {
+            if e.status() == StatusCode::InternalServerError {
+                e.set_status(StatusCode::BadRequest);
+            }
+            Err(e)
+        }
+    };
+
+    let res = if limit_exceeded.load(Ordering::Acquire) {
+        let max = opts.max_body_length.unwrap_or_default();
+        Err(crate::format_err_status!(413, "Body exceeds {} bytes", max))
+    } else {
+        res
+    };
+
+    let mut res = match res {
+        Ok(res) => res,
+        Err(e) => opts.render_error(&e),
+    };
+
+    if let Some(priority) = res.header(PRIORITY) {
+        respond.set_priority(Priority::parse(priority.last().as_str().as_bytes()));
+    }
+
+    match tunnel {
+        Some(recv) if res.status().is_success() && res.has_upgrade() => {
+            let upgrade_sender = res.send_upgrade();
+            let head = response_head(&res).body(())?;
+            let send = respond.send_response(head, false)?;
+            upgrade_sender
+                .send(Connection::new(Tunnel::new(recv, send)))
+                .await;
+            Ok(())
+        }
+        _ => send_response(res, &mut respond, opts.write_timeout).await,
+    }
+}
+
+pub(crate) fn request_from_h2(
+    req: http::Request<RecvStream>,
+    info: Option<&ConnectionInfo>,
+    opts: &ServerOptions,
+    limit_exceeded: Arc<AtomicBool>,
+) -> crate::common::http_types::Result<Request> {
+    let (parts, body) = req.into_parts();
+    let mut req = request_head_from_h2(parts, info)?;
+
+    let len = match req.header(CONTENT_LENGTH) {
+        Some(len) => Some(len.last().as_str().parse::<usize>()?),
+        None => None,
+    };
+
+    if let (Some(max), Some(len)) = (opts.max_body_length, len) {
+        crate::ensure_status!(len as u64 <= max, 413, "Body exceeds {} bytes", max);
+    }
+
+    let trailers = req.send_trailers();
+    let body = RecvBody::new(body, trailers).with_max_len(opts.max_body_length, limit_exceeded);
+    let reader = BufferReader::new(IoTimeout::new(body, opts.read_timeout));
+    req.set_body(Body::from_reader(reader, len));
+
+    Ok(req)
+}
+
+fn request_head_from_h2(
+    parts: http::request::Parts,
+    info: Option<&ConnectionInfo>,
+) -> crate::common::http_types::Result<Request> {
+    let method = Method::from_str(parts.method.as_str())?;
+
+    let authority = match parts.uri.authority() {
+        Some(authority) => authority.as_str(),
+        None => match parts.headers.get(HOST.as_str()) {
+            Some(host) => std::str::from_utf8(host.as_bytes())?,
+            None => "localhost",
+        },
+    };
+    let scheme = parts.uri.scheme_str().unwrap_or("http");
+    let path = parts
+        .uri
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or("/");
+    let url = Url::parse(&format!("{}://{}{}", scheme, authority, path))?;
+
+    let mut req = Request::new(method, url);
+    req.set_version(Some(Version::Http2_0));
+
+    for (name, value) in parts.headers.iter() {
+        req.append_header(name.as_str(), std::str::from_utf8(value.as_bytes())?);
+    }
+
+    if let Some(info) = info {
+        info.apply(&mut req);
+    }
+
+    if let Some(protocol) = parts.extensions.get::<Protocol>() {
+        req.ext_mut().insert(protocol.clone());
+    }
+
+    Ok(req)
+}
+
+fn response_head(res: &Response) -> http::response::Builder {
+    let mut head = http::Response::builder().status(u16::from(res.status()));
+    for (name, values) in res.iter() {
+        if CONNECTION_HEADERS.contains(&name.as_str()) {
+            continue;
+        }
+        for value in values.iter() {
+            head = head.header(name.as_str(), value.as_str());
+        }
+    }
+    head
+}
+
+pub(crate) async fn send_response(
+    mut res: Response,
+    respond: &mut SendResponse<Bytes>,
+    write_timeout: Option<Duration>,
+) -> crate::common::http_types::Result<()> {
+    let mut head = response_head(&res);
+
+    let body = res.take_body();
+    if let (Some(len), None) = (body.len(), res.header(CONTENT_LENGTH)) {
+        head = head.header(CONTENT_LENGTH.as_str(), len);
+    }
+    let head = head.body(())?;
+
+    let end_of_stream = body.is_empty() == Some(true) && !res.has_trailers();
+
+    let mut stream = respond.send_response(head, end_of_stream)?;
+    if end_of_stream {
+        return Ok(());
+    }
+
+    let trailers = if res.has_trailers() {
+        Some(res.recv_trailers())
+    } else {
+        None
+    };
+    send_stream(body, trailers, &mut stream, write_timeout).await
+}
+
+pub(crate) async fn send_request(
+    sender: SendRequest<Bytes>,
+    mut req: Request,
+) -> crate::common::http_types::Result<Response> {
+    let mut head = http::Request::builder()
+        .method(req.method().as_ref())
+        .uri(req.url().as_str());
+    for (name, values) in req.iter() {
+        if CONNECTION_HEADERS.contains(&name.as_str()) || *name == HOST {
+            continue;
+        }
+        for value in values.iter() {
+            head = head.header(name.as_str(), value.as_str());
+        }
+    }
+
+    let body = req.take_body();
+    if let (Some(len), None) = (body.len(), req.header(CONTENT_LENGTH)) {
+        head = head.header(CONTENT_LENGTH.as_str(), len);
+    }
+    let head = head.body(())?;
+
+    let end_of_stream = body.is_empty() == Some(true) && !req.has_trailers();
+
+    let mut sender = sender.ready().await?;
+    let (response, mut stream) = sender.send_request(head, end_of_stream)?;
+    if !end_of_stream {
+        let trailers = if req.has_trailers() {
+            Some(req.recv_trailers())
+        } else {
+            None
+        };
+        send_stream(body, trailers, &mut stream, None).await?;
+    }
+
+    response_from_h2(response.await?)
+}
+
+pub(crate) fn response_from_h2(
+    res: http::Response<RecvStream>,
+) -> crate::common::http_types::Result<Response> {
+    let (parts, body) = res.into_parts();
+
+    let mut res = Response::new(parts.status.as_u16());
+    res.set_version(Some(Version::Http2_0));
+
+    for (name, value) in parts.headers.iter() {
+        res.append_header(name.as_str(), std::str::from_utf8(value.as_bytes())?);
+    }
+
+    let len = match res.header(CONTENT_LENGTH) {
+        Some(len) => Some(len.last().as_str().parse::<usize>()?),
+        None => None,
+    };
+
+    let trailers = res.send_trailers();
+    let reader = BufferReader::new(RecvBody::new(body, trailers));
+    res.set_body(Body::from_reader(reader, len));
+
+    Ok(res)
+}
+
+async fn send_stream(
+    body: Body,
+    trailers: Option<trailers::Receiver>,
+    stream: &mut SendStream<Bytes>,
+    write_timeout: Option<Duration>,
+) -> crate::common::http_types::Result<()> {
+    send_body(body, stream, write_timeout).await?;
+
+    if let Some(trailers) = trailers {
+        if let Some(trailers) = trailers.await {
+            stream.send_trailers(trailers_to_h2(&trailers)?)?;
+            return Ok(());
+        }
+    }
+
+    stream.send_data(Bytes::new(), true)?;
+    Ok(())
+}
+
+async fn send_body(
+    mut body: Body,
+    stream: &mut SendStream<Bytes>,
+    write_timeout: Option<Duration>,
+) -> crate::common::http_types::Result<()> {
+    let mut buf = vec![0; MAX_DATA_CHUNK];
+    loop {
+        let bytes_read = body.read(&mut buf).await?;
+        if bytes_read == 0 {
+            return Ok(());
+        }
+
+        let mut chunk = Bytes::copy_from_slice(&buf[..bytes_read]);
+        while !chunk.is_empty() {
+            stream.reserve_capacity(chunk.len());
+            let capacity = futures_util::future::poll_fn(|cx| stream.poll_capacity(cx));
+            let capacity = match write_timeout {
+                Some(duration) => timeout(duration, capacity)
+                    .await
+                    .map_err(|_| swap::Error::from(swap::ErrorKind::TimedOut))?,
+                None => capacity.await,
+            };
+            let capacity =
+                capacity.ok_or_else(|| format_err!("Stream closed before the body was sent"))??;
+            let len = cmp::min(capacity, chunk.len());
+            stream.send_data(chunk.split_to(len), false)?;
+        }
+    }
+}
+
+fn trailers_to_h2(trailers: &Trailers) -> crate::common::http_types::Result<http::HeaderMap> {
+    let mut map = http::HeaderMap::new();
+    for (name, values) in trailers.iter() {
+        let name = http::header::HeaderName::from_bytes(name.as_str().as_bytes())?;
+        for value in values.iter() {
+            map.append(name.clone(), http::HeaderValue::from_str(value.as_str())?);
+        }
+    }
+    Ok(map)
+}
+
+fn trailers_from_h2(map: &http::HeaderMap) -> swap::Result<Trailers> {
+    let mut trailers = Trailers::new();
+    for (name, value) in map.iter() {
+        let value = std::str::from_utf8(value.as_bytes())
+            .map_err(|e| swap::Error::new(swap::ErrorKind::InvalidData, e))?;
+        trailers.append(name.as_str(), value);
+    }
+    Ok(trailers)
+}
+
+pub(crate) struct RecvBody {
+    stream: RecvStream,
+    data: Bytes,
+    state: RecvState,
+    received: u64,
+    max_len: Option<u64>,
+    limit_exceeded: Option<Arc<AtomicBool>>,
+}
+
+enum RecvState {
+    Data(Option<trailers::Sender>),
+    Trailers(Option<trailers::Sender>),
+    TrailerSending(Pin<Box<dyn Future<Output = ()> + 'static + Send + Sync>>),
+    Done,
+}
+
+impl fmt::Debug for RecvBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecvBody")
+            .field("stream", &self.stream.stream_id())
+            .field("buffered", &self.data.len())
+            .finish()
+    }
+}
+
+impl RecvBody {
+    pub(crate) fn new(stream: RecvStream, trailers: trailers::Sender) -> Self {
+        Self {
+            stream,
+            data: Bytes::new(),
+            state: RecvState::Data(Some(trailers)),
+            received: 0,
+            max_len: None,
+            limit_exceeded: None,
+        }
+    }
+
+    pub(crate) fn with_max_len(
+        mut self,
+        max_len: Option<u64>,
+        limit_exceeded: Arc<AtomicBool>,
+    ) -> Self {
+        self.max_len = max_len;
+        self.limit_exceeded = Some(limit_exceeded);
+        self
+    }
+}
+
+fn to_io_error(e: crate::proto::h2::Error) -> swap::Error {
+    swap::Error::new(swap::ErrorKind::Other, e)
+}
+
+impl AsyncRead for RecvBody {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<swap::Result<usize>> {
+        let this = &mut *self;
+        loop {
+            if !this.data.is_empty() {
+                let n = cmp::min(buf.len(), this.data.len());
+                buf[..n].copy_from_slice(&this.data[..n]);
+                this.data.advance(n);
+                return Poll::Ready(Ok(n));
+            }
+
+            match &mut this.state {
+                RecvState::Data(sender) => match ready!(this.stream.poll_data(cx)) {
+                    Some(Ok(data)) => {
+                        this.stream
+                            .flow_control()
+                            .release_capacity(data.len())
+                            .map_err(to_io_error)?;
+                        this.received += data.len() as u64;
+                        if this.max_len.map_or(false, |max| this.received > max) {
+                            if let Some(limit_exceeded) = &this.limit_exceeded {
+                                limit_exceeded.store(true, Ordering::Release);
+                            }
+                            return Poll::Ready(Err(swap::Error::new(
+                                swap::ErrorKind::InvalidData,
+                                "body exceeds the maximum length",
+                            )));
+                        }
+                        this.data = data;
+                    }
+                    Some(Err(e)) => return Poll::Ready(Err(to_io_error(e))),
+                    None => this.state = RecvState::Trailers(sender.take()),
+                },
+                RecvState::Trailers(sender) => {
+                    let sender = sender.take();
+                    let map = ready!(this.stream.poll_trailers(cx)).map_err(to_io_error)?;
+                    this.state = match (map, sender) {
+                        (Some(map), Some(sender)) => {
+                            let trailers = trailers_from_h2(&map)?;
+                            RecvState::TrailerSending(Box::pin(sender.send(trailers)))
+                        }
+                        _ => RecvState::Done,
+                    };
+                }
+                RecvState::TrailerSending(fut) => {
+                    ready!(fut.as_mut().poll(cx));
+                    this.state = RecvState::Done;
+                }
+                RecvState::Done => return Poll::Ready(Ok(0)),
+            }
+        }
+    }
+}
+
+pub(crate) struct Tunnel {
+    recv: RecvStream,
+    send: SendStream<Bytes>,
+    data: Bytes,
+    closed: bool,
+}
+
+impl fmt::Debug for Tunnel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tunnel")
+            .field("stream", &self.recv.stream_id())
+            .field("buffered", &self.data.len())
+            .field("closed", &self.closed)
+            .finish()
+    }
+}
+
+impl Tunnel {
+    pub(crate) fn new(recv: RecvStream, send: SendStream<Bytes>) -> Self {
+        Self {
+            recv,
+            send,
+            data: Bytes::new(),
+            closed: false,
+        }
+    }
+}
+
+impl AsyncRead for Tunnel {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<swap::Result<usize>> {
+        let this = &mut *self;
+        while this.data.is_empty() {
+            match ready!(this.recv.poll_data(cx)) {
+                Some(Ok(data)) => {
+                    this.recv
+                        .flow_control()
+                        .release_capacity(data.len())
+                        .map_err(to_io_error)?;
+                    this.data = data;
+                }
+                Some(Err(e)) => return Poll::Ready(Err(to_io_error(e))),
+                None => return Poll::Ready(Ok(0)),
+            }
+        }
+
+        let n = cmp::min(buf.len(), this.data.len());
+        buf[..n].copy_from_slice(&this.data[..n]);
+        this.data.advance(n);
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl AsyncWrite for Tunnel {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<swap::Result<usize>> {
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let len = cmp::min(buf.len(), MAX_DATA_CHUNK);
+        self.send.reserve_capacity(len);
+        let capacity = match ready!(self.send.poll_capacity(cx)) {
+            Some(capacity) => capacity.map_err(to_io_error)?,
+            None => return Poll::Ready(Err(swap::ErrorKind::BrokenPipe.into())),
+        };
+
+        let len = cmp::min(capacity, len);
+        self.send
+            .send_data(Bytes::copy_from_slice(&buf[..len]), false)
+            .map_err(to_io_error)?;
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<swap::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<swap::Result<()>> {
+        if !self.closed {
+            self.closed = true;
+            self.send
+                .send_data(Bytes::new(), true)
+                .map_err(to_io_error)?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
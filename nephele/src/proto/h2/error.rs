@@ -14,6 +14,7 @@ enum Kind {
     Proto(Reason),
     User(UserError),
     Io(io::Error),
+    KeepAliveTimedOut,
 }
 
 impl Error {
@@ -31,6 +32,13 @@ impl Error {
         }
     }
 
+    pub fn is_keep_alive_timeout(&self) -> bool {
+        match self.kind {
+            Kind::KeepAliveTimedOut => true,
+            _ => false,
+        }
+    }
+
     pub fn get_io(&self) -> Option<&io::Error> {
         match self.kind {
             Kind::Io(ref e) => Some(e),
@@ -60,6 +68,7 @@ impl From<proto::Error> for Error {
             kind: match src {
                 Proto(reason) => Kind::Proto(reason),
                 Io(e) => Kind::Io(e),
+                KeepAliveTimedOut => Kind::KeepAliveTimedOut,
             },
         }
     }
@@ -99,6 +108,7 @@ impl fmt::Display for Error {
             Proto(ref reason) => write!(fmt, "protocol error: {}", reason),
             User(ref e) => write!(fmt, "user error: {}", e),
             Io(ref e) => fmt::Display::fmt(e, fmt),
+            KeepAliveTimedOut => fmt.write_str("keep-alive timed out"),
         }
     }
 }
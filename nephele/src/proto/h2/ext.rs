@@ -0,0 +1,125 @@
+use bytes::Bytes;
+use std::fmt;
+
+use crate::proto::h2::hpack::BytesStr;
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Protocol {
+    value: BytesStr,
+}
+
+impl Protocol {
+    pub const fn from_static(value: &'static str) -> Self {
+        Self {
+            value: BytesStr::from_static(value),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        self.value.as_str()
+    }
+
+    pub(crate) fn try_from(bytes: Bytes) -> Result<Self, std::str::Utf8Error> {
+        Ok(Self {
+            value: BytesStr::try_from(bytes)?,
+        })
+    }
+}
+
+impl<'a> From<&'a str> for Protocol {
+    fn from(value: &'a str) -> Self {
+        Self {
+            value: unsafe {
+                BytesStr::from_utf8_unchecked(Bytes::copy_from_slice(value.as_bytes()))
+            },
+        }
+    }
+}
+
+impl AsRef<[u8]> for Protocol {
+    fn as_ref(&self) -> &[u8] {
+        self.value.as_ref()
+    }
+}
+
+impl fmt::Debug for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Priority {
+    urgency: u8,
+    incremental: bool,
+}
+
+impl Priority {
+    pub const MAX_URGENCY: u8 = 7;
+
+    pub const fn new(urgency: u8, incremental: bool) -> Self {
+        Self {
+            urgency: if urgency > Self::MAX_URGENCY {
+                Self::MAX_URGENCY
+            } else {
+                urgency
+            },
+            incremental,
+        }
+    }
+
+    pub fn urgency(&self) -> u8 {
+        self.urgency
+    }
+
+    pub fn is_incremental(&self) -> bool {
+        self.incremental
+    }
+
+    pub fn parse(value: &[u8]) -> Self {
+        let mut priority = Self::default();
+        let value = match std::str::from_utf8(value) {
+            Ok(value) => value,
+            Err(_) => return priority,
+        };
+
+        for member in value.split(',') {
+            let member = member.split(';').next().unwrap_or_default().trim();
+            let (key, val) = match member.split_once('=') {
+                Some((key, val)) => (key.trim(), Some(val.trim())),
+                None => (member, None),
+            };
+
+            match (key, val) {
+                ("u", Some(val)) => {
+                    if let Ok(urgency) = val.parse::<u8>() {
+                        if urgency <= Self::MAX_URGENCY {
+                            priority.urgency = urgency;
+                        }
+                    }
+                }
+                ("i", None) | ("i", Some("?1")) => priority.incremental = true,
+                ("i", Some("?0")) => priority.incremental = false,
+                _ => {}
+            }
+        }
+
+        priority
+    }
+}
+
+impl Default for Priority {
+    fn default() -> Self {
+        Self::new(3, false)
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "u={}", self.urgency)?;
+        if self.incremental {
+            f.write_str(", i")?;
+        }
+        Ok(())
+    }
+}
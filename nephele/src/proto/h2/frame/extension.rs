@@ -0,0 +1,88 @@
+use bytes::{BufMut, Bytes};
+use std::fmt;
+
+use crate::proto::h2::frame::{self, Head, Kind, StreamId};
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct ExtensionFrame {
+    kind: u8,
+    flags: u8,
+    stream_id: StreamId,
+    payload: Bytes,
+}
+
+impl ExtensionFrame {
+    pub fn new(kind: u8, flags: u8, stream_id: u32, payload: Bytes) -> ExtensionFrame {
+        ExtensionFrame {
+            kind,
+            flags,
+            stream_id: stream_id.into(),
+            payload,
+        }
+    }
+
+    pub fn kind(&self) -> u8 {
+        self.kind
+    }
+
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    pub fn stream_id(&self) -> u32 {
+        self.stream_id.into()
+    }
+
+    pub fn payload(&self) -> &Bytes {
+        &self.payload
+    }
+
+    pub fn into_payload(self) -> Bytes {
+        self.payload
+    }
+
+    pub(crate) fn is_extension_kind(kind: u8) -> bool {
+        Kind::new(kind) == Kind::Unknown
+    }
+
+    pub(crate) fn load(head: Head, kind: u8, payload: Bytes) -> ExtensionFrame {
+        debug_assert_eq!(head.kind(), Kind::Unknown);
+
+        ExtensionFrame {
+            kind,
+            flags: head.flag(),
+            stream_id: head.stream_id(),
+            payload,
+        }
+    }
+
+    pub(crate) fn encode<B: BufMut>(&self, dst: &mut B) {
+        tracing::trace!(
+            "encoding extension frame; kind={:#x}; len={}",
+            self.kind,
+            self.payload.len()
+        );
+        dst.put_uint(self.payload.len() as u64, 3);
+        dst.put_u8(self.kind);
+        dst.put_u8(self.flags);
+        dst.put_u32(self.stream_id.into());
+        dst.put_slice(&self.payload);
+    }
+}
+
+impl<B> From<ExtensionFrame> for frame::Frame<B> {
+    fn from(src: ExtensionFrame) -> Self {
+        frame::Frame::Extension(src)
+    }
+}
+
+impl fmt::Debug for ExtensionFrame {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("ExtensionFrame")
+            .field("kind", &format_args!("{:#x}", self.kind))
+            .field("flags", &format_args!("{:#x}", self.flags))
+            .field("stream_id", &self.stream_id)
+            .field("len", &self.payload.len())
+            .finish()
+    }
+}
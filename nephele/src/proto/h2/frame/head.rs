@@ -22,6 +22,7 @@ pub enum Kind {
     GoAway = 7,
     WindowUpdate = 8,
     Continuation = 9,
+    PriorityUpdate = 16,
     Unknown,
 }
 
@@ -83,6 +84,7 @@ impl Kind {
             7 => Kind::GoAway,
             8 => Kind::WindowUpdate,
             9 => Kind::Continuation,
+            16 => Kind::PriorityUpdate,
             _ => Kind::Unknown,
         }
     }
@@ -4,6 +4,7 @@ use http::{uri, HeaderMap, Method, Request, StatusCode, Uri};
 use std::fmt;
 use std::io::Cursor;
 
+use crate::proto::h2::ext::Protocol;
 use crate::proto::h2::frame::{util, StreamDependency, StreamId};
 use crate::proto::h2::frame::{Error, Frame, Head, Kind};
 use crate::proto::h2::hpack::{self, BytesStr};
@@ -46,6 +47,7 @@ pub struct Pseudo {
     pub scheme: Option<BytesStr>,
     pub authority: Option<BytesStr>,
     pub path: Option<BytesStr>,
+    pub protocol: Option<Protocol>,
     pub status: Option<StatusCode>,
 }
 
@@ -458,7 +460,7 @@ impl Continuation {
 }
 
 impl Pseudo {
-    pub fn request(method: Method, uri: Uri) -> Self {
+    pub fn request(method: Method, uri: Uri, protocol: Option<Protocol>) -> Self {
         let parts = uri::Parts::from(uri);
 
         let mut path = parts
@@ -466,7 +468,7 @@ impl Pseudo {
             .map(|v| Bytes::copy_from_slice(v.as_str().as_bytes()))
             .unwrap_or_else(Bytes::new);
 
-        if path.is_empty() && method != Method::OPTIONS {
+        if path.is_empty() && method != Method::OPTIONS && method != Method::CONNECT {
             path = Bytes::from_static(b"/");
         }
 
@@ -474,7 +476,8 @@ impl Pseudo {
             method: Some(method),
             scheme: None,
             authority: None,
-            path: Some(unsafe { BytesStr::from_utf8_unchecked(path) }),
+            path: Some(unsafe { BytesStr::from_utf8_unchecked(path) }).filter(|p| !p.is_empty()),
+            protocol,
             status: None,
         };
 
@@ -497,6 +500,7 @@ impl Pseudo {
             scheme: None,
             authority: None,
             path: None,
+            protocol: None,
             status: Some(status),
         }
     }
@@ -584,6 +588,10 @@ impl Iterator for Iter {
                 return Some(Path(path));
             }
 
+            if let Some(protocol) = pseudo.protocol.take() {
+                return Some(Protocol(protocol));
+            }
+
             if let Some(status) = pseudo.status.take() {
                 return Some(Status(status));
             }
@@ -767,6 +775,7 @@ impl HeaderBlock {
                 Method(v) => set_pseudo!(method, v),
                 Scheme(v) => set_pseudo!(scheme, v),
                 Path(v) => set_pseudo!(path, v),
+                Protocol(v) => set_pseudo!(protocol, v),
                 Status(v) => set_pseudo!(status, v),
             }
         });
@@ -810,6 +819,7 @@ impl HeaderBlock {
             + pseudo_size!(status)
             + pseudo_size!(authority)
             + pseudo_size!(path)
+            + pseudo_size!(protocol)
             + self
                 .fields
                 .iter()
@@ -844,6 +854,10 @@ impl HeaderBlock {
             return true;
         }
 
+        if pseudo_size!(protocol) > MAX_HEADER_LENGTH {
+            return true;
+        }
+
         for (name, value) in &self.fields {
             if decoded_header_size(name.as_str().len(), value.len()) > MAX_HEADER_LENGTH {
                 return true;
@@ -14,11 +14,13 @@ macro_rules! unpack_octets_4 {
 }
 
 mod data;
+mod extension;
 mod go_away;
 mod head;
 mod headers;
 mod ping;
 mod priority;
+mod priority_update;
 mod reason;
 mod reset;
 mod settings;
@@ -27,11 +29,13 @@ mod util;
 mod window_update;
 
 pub use data::Data;
+pub use extension::ExtensionFrame;
 pub use go_away::GoAway;
 pub use head::{Head, Kind};
 pub use headers::{parse_u64, Continuation, Headers, Pseudo, PushPromise, PushPromiseHeaderError};
 pub use ping::Ping;
 pub use priority::{Priority, StreamDependency};
+pub use priority_update::PriorityUpdate;
 pub use reason::Reason;
 pub use reset::Reset;
 pub use settings::Settings;
@@ -55,12 +59,14 @@ pub enum Frame<T = Bytes> {
     Data(Data<T>),
     Headers(Headers),
     Priority(Priority),
+    PriorityUpdate(PriorityUpdate),
     PushPromise(PushPromise),
     Settings(Settings),
     Ping(Ping),
     GoAway(GoAway),
     WindowUpdate(WindowUpdate),
     Reset(Reset),
+    Extension(ExtensionFrame),
 }
 
 impl<T> Frame<T> {
@@ -74,12 +80,14 @@ impl<T> Frame<T> {
             Data(frame) => frame.map(f).into(),
             Headers(frame) => frame.into(),
             Priority(frame) => frame.into(),
+            PriorityUpdate(frame) => frame.into(),
             PushPromise(frame) => frame.into(),
             Settings(frame) => frame.into(),
             Ping(frame) => frame.into(),
             GoAway(frame) => frame.into(),
             WindowUpdate(frame) => frame.into(),
             Reset(frame) => frame.into(),
+            Extension(frame) => frame.into(),
         }
     }
 }
@@ -92,12 +100,14 @@ impl<T> fmt::Debug for Frame<T> {
             Data(ref frame) => fmt::Debug::fmt(frame, fmt),
             Headers(ref frame) => fmt::Debug::fmt(frame, fmt),
             Priority(ref frame) => fmt::Debug::fmt(frame, fmt),
+            PriorityUpdate(ref frame) => fmt::Debug::fmt(frame, fmt),
             PushPromise(ref frame) => fmt::Debug::fmt(frame, fmt),
             Settings(ref frame) => fmt::Debug::fmt(frame, fmt),
             Ping(ref frame) => fmt::Debug::fmt(frame, fmt),
             GoAway(ref frame) => fmt::Debug::fmt(frame, fmt),
             WindowUpdate(ref frame) => fmt::Debug::fmt(frame, fmt),
             Reset(ref frame) => fmt::Debug::fmt(frame, fmt),
+            Extension(ref frame) => fmt::Debug::fmt(frame, fmt),
         }
     }
 }
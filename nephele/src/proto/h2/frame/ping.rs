@@ -14,6 +14,8 @@ pub struct Ping {
 
 const SHUTDOWN_PAYLOAD: Payload = [0x0b, 0x7b, 0xa2, 0xf0, 0x8b, 0x9b, 0xfe, 0x54];
 const USER_PAYLOAD: Payload = [0x3b, 0x7c, 0xdb, 0x7a, 0x0b, 0x87, 0x16, 0xb4];
+const BDP_PAYLOAD: Payload = [0x62, 0x64, 0x70, 0x5f, 0x70, 0x69, 0x6e, 0x67];
+const KEEP_ALIVE_PAYLOAD: Payload = [0x6b, 0x65, 0x65, 0x70, 0x61, 0x6c, 0x69, 0x76];
 
 impl Ping {
     #[cfg(feature = "unstable")]
@@ -28,6 +30,18 @@ impl Ping {
     #[cfg(not(feature = "unstable"))]
     pub(crate) const USER: Payload = USER_PAYLOAD;
 
+    #[cfg(feature = "unstable")]
+    pub const BDP: Payload = BDP_PAYLOAD;
+
+    #[cfg(not(feature = "unstable"))]
+    pub(crate) const BDP: Payload = BDP_PAYLOAD;
+
+    #[cfg(feature = "unstable")]
+    pub const KEEP_ALIVE: Payload = KEEP_ALIVE_PAYLOAD;
+
+    #[cfg(not(feature = "unstable"))]
+    pub(crate) const KEEP_ALIVE: Payload = KEEP_ALIVE_PAYLOAD;
+
     pub fn new(payload: Payload) -> Ping {
         Ping {
             ack: false,
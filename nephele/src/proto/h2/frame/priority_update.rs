@@ -0,0 +1,74 @@
+use bytes::{BufMut, Bytes};
+use std::fmt;
+
+use crate::proto::h2::ext::Priority;
+use crate::proto::h2::frame::{self, Error, Head, Kind, StreamId};
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct PriorityUpdate {
+    prioritized_id: StreamId,
+    field_value: Bytes,
+}
+
+impl PriorityUpdate {
+    pub fn new(prioritized_id: StreamId, priority: Priority) -> Self {
+        PriorityUpdate {
+            prioritized_id,
+            field_value: Bytes::from(priority.to_string()),
+        }
+    }
+
+    pub fn prioritized_id(&self) -> StreamId {
+        self.prioritized_id
+    }
+
+    pub fn priority(&self) -> Priority {
+        Priority::parse(&self.field_value)
+    }
+
+    pub fn load(head: Head, payload: &[u8]) -> Result<Self, Error> {
+        debug_assert_eq!(head.kind(), Kind::PriorityUpdate);
+
+        if !head.stream_id().is_zero() {
+            return Err(Error::InvalidStreamId);
+        }
+
+        if payload.len() < 4 {
+            return Err(Error::BadFrameSize);
+        }
+
+        let (prioritized_id, _) = StreamId::parse(&payload[..4]);
+
+        if prioritized_id.is_zero() {
+            return Err(Error::InvalidStreamId);
+        }
+
+        Ok(PriorityUpdate {
+            prioritized_id,
+            field_value: Bytes::copy_from_slice(&payload[4..]),
+        })
+    }
+
+    pub fn encode<B: BufMut>(&self, dst: &mut B) {
+        tracing::trace!("encoding PRIORITY_UPDATE; id={:?}", self.prioritized_id);
+        let head = Head::new(Kind::PriorityUpdate, 0, StreamId::zero());
+        head.encode(4 + self.field_value.len(), dst);
+        dst.put_u32(self.prioritized_id.into());
+        dst.put_slice(&self.field_value);
+    }
+}
+
+impl<B> From<PriorityUpdate> for frame::Frame<B> {
+    fn from(src: PriorityUpdate) -> Self {
+        frame::Frame::PriorityUpdate(src)
+    }
+}
+
+impl fmt::Debug for PriorityUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PriorityUpdate")
+            .field("prioritized_id", &self.prioritized_id)
+            .field("field_value", &self.field_value)
+            .finish()
+    }
+}
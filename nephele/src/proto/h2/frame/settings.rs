@@ -12,6 +12,8 @@ pub struct Settings {
     initial_window_size: Option<u32>,
     max_frame_size: Option<u32>,
     max_header_list_size: Option<u32>,
+    enable_connect_protocol: Option<u32>,
+    extensions: Vec<(u16, u32)>,
 }
 
 #[derive(Debug)]
@@ -22,6 +24,8 @@ pub enum Setting {
     InitialWindowSize(u32),
     MaxFrameSize(u32),
     MaxHeaderListSize(u32),
+    EnableConnectProtocol(u32),
+    Unknown(u16, u32),
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Default)]
@@ -99,6 +103,29 @@ impl Settings {
         self.header_table_size
     }
 
+    pub fn is_extended_connect_protocol_enabled(&self) -> Option<bool> {
+        self.enable_connect_protocol.map(|val| val != 0)
+    }
+
+    pub fn set_enable_connect_protocol(&mut self, val: Option<u32>) {
+        self.enable_connect_protocol = val;
+    }
+
+    pub fn extensions(&self) -> &[(u16, u32)] {
+        &self.extensions
+    }
+
+    pub fn set_extension(&mut self, id: u16, val: Option<u32>) {
+        if !matches!(Setting::from_id(id, 0), Setting::Unknown(..)) {
+            tracing::warn!("ignoring known setting as extension; id={:#x}", id);
+            return;
+        }
+        self.extensions.retain(|(ext, _)| *ext != id);
+        if let Some(val) = val {
+            self.extensions.push((id, val));
+        }
+    }
+
     pub fn load(head: Head, payload: &[u8]) -> Result<Settings, Error> {
         use self::Setting::*;
 
@@ -128,10 +155,10 @@ impl Settings {
 
         for raw in payload.chunks(6) {
             match Setting::load(raw) {
-                Some(HeaderTableSize(val)) => {
+                HeaderTableSize(val) => {
                     settings.header_table_size = Some(val);
                 }
-                Some(EnablePush(val)) => match val {
+                EnablePush(val) => match val {
                     0 | 1 => {
                         settings.enable_push = Some(val);
                     }
@@ -139,27 +166,38 @@ impl Settings {
                         return Err(Error::InvalidSettingValue);
                     }
                 },
-                Some(MaxConcurrentStreams(val)) => {
+                MaxConcurrentStreams(val) => {
                     settings.max_concurrent_streams = Some(val);
                 }
-                Some(InitialWindowSize(val)) => {
+                InitialWindowSize(val) => {
                     if val as usize > MAX_INITIAL_WINDOW_SIZE {
                         return Err(Error::InvalidSettingValue);
                     } else {
                         settings.initial_window_size = Some(val);
                     }
                 }
-                Some(MaxFrameSize(val)) => {
+                MaxFrameSize(val) => {
                     if val < DEFAULT_MAX_FRAME_SIZE || val > MAX_MAX_FRAME_SIZE {
                         return Err(Error::InvalidSettingValue);
                     } else {
                         settings.max_frame_size = Some(val);
                     }
                 }
-                Some(MaxHeaderListSize(val)) => {
+                MaxHeaderListSize(val) => {
                     settings.max_header_list_size = Some(val);
                 }
-                None => {}
+                EnableConnectProtocol(val) => match val {
+                    0 | 1 => {
+                        settings.enable_connect_protocol = Some(val);
+                    }
+                    _ => {
+                        return Err(Error::InvalidSettingValue);
+                    }
+                },
+                Unknown(id, val) => {
+                    settings.extensions.retain(|(ext, _)| *ext != id);
+                    settings.extensions.push((id, val));
+                }
             }
         }
 
@@ -212,6 +250,14 @@ impl Settings {
         if let Some(v) = self.max_header_list_size {
             f(MaxHeaderListSize(v));
         }
+
+        if let Some(v) = self.enable_connect_protocol {
+            f(EnableConnectProtocol(v));
+        }
+
+        for &(id, v) in &self.extensions {
+            f(Unknown(id, v));
+        }
     }
 }
 
@@ -245,6 +291,12 @@ impl fmt::Debug for Settings {
             Setting::MaxHeaderListSize(v) => {
                 builder.field("max_header_list_size", &v);
             }
+            Setting::EnableConnectProtocol(v) => {
+                builder.field("enable_connect_protocol", &v);
+            }
+            Setting::Unknown(id, v) => {
+                builder.field(&format!("{:#x}", id), &v);
+            }
         });
 
         builder.finish()
@@ -252,21 +304,22 @@ impl fmt::Debug for Settings {
 }
 
 impl Setting {
-    pub fn from_id(id: u16, val: u32) -> Option<Setting> {
+    pub fn from_id(id: u16, val: u32) -> Setting {
         use self::Setting::*;
 
         match id {
-            1 => Some(HeaderTableSize(val)),
-            2 => Some(EnablePush(val)),
-            3 => Some(MaxConcurrentStreams(val)),
-            4 => Some(InitialWindowSize(val)),
-            5 => Some(MaxFrameSize(val)),
-            6 => Some(MaxHeaderListSize(val)),
-            _ => None,
+            1 => HeaderTableSize(val),
+            2 => EnablePush(val),
+            3 => MaxConcurrentStreams(val),
+            4 => InitialWindowSize(val),
+            5 => MaxFrameSize(val),
+            6 => MaxHeaderListSize(val),
+            8 => EnableConnectProtocol(val),
+            _ => Unknown(id, val),
         }
     }
 
-    fn load(raw: &[u8]) -> Option<Setting> {
+    fn load(raw: &[u8]) -> Setting {
         let id: u16 = (u16::from(raw[0]) << 8) | u16::from(raw[1]);
         let val: u32 = unpack_octets_4!(raw, 2, u32);
 
@@ -283,6 +336,8 @@ impl Setting {
             InitialWindowSize(v) => (4, v),
             MaxFrameSize(v) => (5, v),
             MaxHeaderListSize(v) => (6, v),
+            EnableConnectProtocol(v) => (8, v),
+            Unknown(id, v) => (id, v),
         };
 
         dst.put_u16(kind);
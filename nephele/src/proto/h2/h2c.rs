@@ -0,0 +1,25 @@
+use bytes::BytesMut;
+
+use crate::common::http_types::Result;
+use crate::format_err;
+use crate::proto::h2::frame::{Head, Kind, StreamId};
+
+pub(crate) use crate::proto::h2::frame::Settings;
+
+const FRAME_HEADER_LEN: usize = 9;
+
+pub(crate) fn decode_settings(value: &str) -> Result<Settings> {
+    let value = value.trim().trim_end_matches('=');
+    let payload = base64::decode_config(value, base64::URL_SAFE_NO_PAD)
+        .map_err(|_| format_err!("Invalid HTTP2-Settings encoding"))?;
+
+    let head = Head::new(Kind::Settings, 0, StreamId::zero());
+    let settings = Settings::load(head, &payload);
+    settings.map_err(|e| format_err!("Invalid HTTP2-Settings: {:?}", e))
+}
+
+pub(crate) fn encode_settings(settings: &Settings) -> String {
+    let mut buf = BytesMut::new();
+    settings.encode(&mut buf);
+    base64::encode_config(&buf[FRAME_HEADER_LEN..], base64::URL_SAFE_NO_PAD)
+}
@@ -30,12 +30,18 @@ mod frame;
 pub mod frame;
 
 pub mod client;
+mod endpoint;
+pub mod ext;
+pub(crate) mod h2c;
 pub mod server;
 mod share;
 
+pub use crate::proto::h2::endpoint::{accept, accept_with_opts};
+pub(crate) use crate::proto::h2::endpoint::{accept_h2c, response_from_h2, send_request};
 pub use crate::proto::h2::error::{Error, Reason};
+pub use crate::proto::h2::frame::ExtensionFrame;
 pub use crate::proto::h2::share::{
-    FlowControl, Ping, PingPong, Pong, RecvStream, SendStream, StreamId,
+    Extensions, FlowControl, Ping, PingPong, Pong, RecvStream, SendStream, StreamId,
 };
 
 #[cfg(feature = "unstable")]
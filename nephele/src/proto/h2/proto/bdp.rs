@@ -0,0 +1,132 @@
+use std::mem;
+use std::time::{Duration, Instant};
+
+use crate::proto::h2::proto::WindowSize;
+
+const BDP_LIMIT: WindowSize = 16 * 1024 * 1024;
+const INITIAL_PING_DELAY: Duration = Duration::from_millis(100);
+const MAX_PING_DELAY: Duration = Duration::from_secs(10);
+const MIN_RTT: Duration = Duration::from_micros(100);
+
+#[derive(Debug)]
+pub(crate) struct Bdp {
+    estimate: WindowSize,
+    bytes: usize,
+    ping_sent_at: Option<Instant>,
+    next_ping_at: Option<Instant>,
+    ping_delay: Duration,
+    stable_count: u32,
+    rtt: f64,
+    max_bandwidth: f64,
+    pending_window: Option<WindowSize>,
+}
+
+impl Bdp {
+    pub(crate) fn new(initial_window: WindowSize) -> Self {
+        Bdp {
+            estimate: initial_window,
+            bytes: 0,
+            ping_sent_at: None,
+            next_ping_at: None,
+            ping_delay: INITIAL_PING_DELAY,
+            stable_count: 0,
+            rtt: 0.0,
+            max_bandwidth: 0.0,
+            pending_window: None,
+        }
+    }
+
+    pub(crate) fn estimate(&self) -> WindowSize {
+        self.estimate
+    }
+
+    pub(crate) fn observe_window(&mut self, window: WindowSize) {
+        self.estimate = self.estimate.max(window.min(BDP_LIMIT));
+    }
+
+    pub(crate) fn recv_data(&mut self, len: usize) -> bool {
+        if let Some(at) = self.next_ping_at {
+            if Instant::now() < at {
+                return false;
+            }
+            self.next_ping_at = None;
+        }
+
+        self.bytes += len;
+
+        if self.ping_sent_at.is_some() {
+            return false;
+        }
+
+        self.ping_sent_at = Some(Instant::now());
+        true
+    }
+
+    pub(crate) fn recv_pong(&mut self) -> Option<WindowSize> {
+        let rtt = self.ping_sent_at.take()?.elapsed();
+        let bytes = mem::replace(&mut self.bytes, 0);
+        self.next_ping_at = Some(Instant::now() + self.ping_delay);
+
+        let window = self.calculate(bytes, rtt)?;
+        tracing::debug!(
+            "bdp estimate increased; window={}; rtt={:?}",
+            window,
+            Duration::from_secs_f64(self.rtt)
+        );
+        Some(window)
+    }
+
+    pub(crate) fn take_pending_window(&mut self) -> Option<WindowSize> {
+        self.pending_window.take()
+    }
+
+    pub(crate) fn defer_window(&mut self, window: WindowSize) {
+        self.pending_window = Some(window);
+    }
+
+    fn calculate(&mut self, bytes: usize, rtt: Duration) -> Option<WindowSize> {
+        if self.estimate >= BDP_LIMIT {
+            self.stabilize_delay();
+            return None;
+        }
+
+        let rtt = rtt.max(MIN_RTT).as_secs_f64();
+        if self.rtt == 0.0 {
+            self.rtt = rtt;
+        } else {
+            self.rtt += (rtt - self.rtt) * 0.125;
+        }
+
+        let bandwidth = bytes as f64 / (self.rtt * 1.5);
+        tracing::trace!(
+            "bdp sample; bytes={}; rtt={}; bandwidth={}",
+            bytes,
+            self.rtt,
+            bandwidth
+        );
+
+        if bandwidth < self.max_bandwidth {
+            self.stabilize_delay();
+            return None;
+        }
+        self.max_bandwidth = bandwidth;
+
+        if bytes >= self.estimate as usize * 2 / 3 {
+            self.estimate = (bytes * 2).min(BDP_LIMIT as usize) as WindowSize;
+            Some(self.estimate)
+        } else {
+            self.stabilize_delay();
+            None
+        }
+    }
+
+    fn stabilize_delay(&mut self) {
+        if self.ping_delay < MAX_PING_DELAY {
+            self.stable_count += 1;
+            if self.stable_count >= 2 {
+                self.ping_delay = (self.ping_delay * 4).min(MAX_PING_DELAY);
+                self.stable_count = 0;
+            }
+        }
+    }
+}
@@ -23,6 +23,9 @@ where
     codec: Codec<T, Prioritized<B>>,
     go_away: GoAway,
     ping_pong: PingPong,
+    extensions: Extensions,
+    bdp: Option<Bdp>,
+    keep_alive: Option<KeepAlive>,
     settings: Settings,
     streams: Streams<B, P>,
     span: tracing::Span,
@@ -35,6 +38,10 @@ pub(crate) struct Config {
     pub initial_max_send_streams: usize,
     pub reset_stream_duration: Duration,
     pub reset_stream_max: usize,
+    pub adaptive_window: bool,
+    pub keep_alive_interval: Option<Duration>,
+    pub keep_alive_timeout: Duration,
+    pub keep_alive_while_idle: bool,
     pub settings: frame::Settings,
 }
 
@@ -52,14 +59,19 @@ where
     B: Buf,
 {
     pub fn new(codec: Codec<T, Prioritized<B>>, config: Config) -> Connection<T, P, B> {
+        let local_init_window_sz = config
+            .settings
+            .initial_window_size()
+            .unwrap_or(DEFAULT_INITIAL_WINDOW_SIZE);
         let streams = Streams::new(streams::Config {
-            local_init_window_sz: config
-                .settings
-                .initial_window_size()
-                .unwrap_or(DEFAULT_INITIAL_WINDOW_SIZE),
+            local_init_window_sz,
             initial_max_send_streams: config.initial_max_send_streams,
             local_next_stream_id: config.next_stream_id,
             local_push_enabled: config.settings.is_push_enabled().unwrap_or(true),
+            extended_connect_protocol_enabled: config
+                .settings
+                .is_extended_connect_protocol_enabled()
+                .unwrap_or(false),
             local_reset_duration: config.reset_stream_duration,
             local_reset_max: config.reset_stream_max,
             remote_init_window_sz: DEFAULT_INITIAL_WINDOW_SIZE,
@@ -74,6 +86,19 @@ where
             codec,
             go_away: GoAway::new(),
             ping_pong: PingPong::new(),
+            extensions: Extensions::new(),
+            bdp: if config.adaptive_window {
+                Some(Bdp::new(local_init_window_sz))
+            } else {
+                None
+            },
+            keep_alive: config.keep_alive_interval.map(|interval| {
+                KeepAlive::new(
+                    interval,
+                    config.keep_alive_timeout,
+                    config.keep_alive_while_idle,
+                )
+            }),
             settings: Settings::new(config.settings),
             streams,
             span: tracing::debug_span!("Connection", peer = %P::NAME),
@@ -82,6 +107,9 @@ where
     }
 
     pub(crate) fn set_target_window_size(&mut self, size: WindowSize) {
+        if let Some(bdp) = self.bdp.as_mut() {
+            bdp.observe_window(size);
+        }
         self.streams.set_target_connection_window_size(size);
     }
 
@@ -91,6 +119,65 @@ where
         self.settings.send_settings(settings)
     }
 
+    pub(crate) fn bdp_estimate(&self) -> Option<WindowSize> {
+        self.bdp.as_ref().map(Bdp::estimate)
+    }
+
+    fn recv_bdp_data(&mut self, len: usize) {
+        let (send_ping, window) = match self.bdp.as_mut() {
+            Some(bdp) => (bdp.recv_data(len), bdp.take_pending_window()),
+            None => return,
+        };
+
+        if send_ping {
+            self.ping_pong.ping_bdp();
+        }
+        if let Some(window) = window {
+            self.set_bdp_window(window);
+        }
+    }
+
+    fn recv_bdp_pong(&mut self) {
+        if let Some(window) = self.bdp.as_mut().and_then(Bdp::recv_pong) {
+            self.set_bdp_window(window);
+        }
+    }
+
+    fn set_bdp_window(&mut self, window: WindowSize) {
+        self.streams.set_target_connection_window_size(window);
+
+        if let Err(e) = self.set_initial_window_size(window) {
+            tracing::trace!("deferring bdp stream window; err={:?}", e);
+            if let Some(bdp) = self.bdp.as_mut() {
+                bdp.defer_window(window);
+            }
+        }
+    }
+
+    fn poll_keep_alive(&mut self, cx: &mut Context) -> Poll<()> {
+        let is_idle = !self.streams.has_streams();
+        let event = match self.keep_alive.as_mut() {
+            Some(keep_alive) => ready!(keep_alive.poll(cx, is_idle)),
+            None => return Poll::Pending,
+        };
+
+        match event {
+            KeepAliveEvent::SendPing => {
+                self.ping_pong.ping_keep_alive();
+            }
+            KeepAliveEvent::TimedOut => {
+                tracing::debug!("keep-alive timed out; closing connection");
+                self.streams.recv_err(&proto::Error::KeepAliveTimedOut);
+                self.go_away_now(Reason::NO_ERROR);
+                if let Poll::Ready(Some(Err(e))) = self.poll_go_away(cx) {
+                    tracing::trace!("failed to flush GOAWAY; err={:?}", e);
+                }
+                self.state = State::Closed(Reason::NO_ERROR);
+            }
+        }
+        Poll::Ready(())
+    }
+
     fn poll_ready(&mut self, cx: &mut Context) -> Poll<Result<(), RecvError>> {
         let _e = self.span.enter();
         let span = tracing::trace_span!("poll_ready");
@@ -98,10 +185,14 @@ where
 
         ready!(self.ping_pong.send_pending_pong(cx, &mut self.codec))?;
         ready!(self.ping_pong.send_pending_ping(cx, &mut self.codec))?;
+        ready!(self.extensions.send_pending(cx, &mut self.codec))?;
         ready!(self
             .settings
             .poll_send(cx, &mut self.codec, &mut self.streams))?;
         ready!(self.streams.send_pending_refusal(cx, &mut self.codec))?;
+        ready!(self
+            .streams
+            .send_pending_priority_updates(cx, &mut self.codec))?;
 
         Poll::Ready(Ok(()))
     }
@@ -131,6 +222,14 @@ where
     }
 
     fn take_error(&mut self, ours: Reason) -> Poll<Result<(), proto::Error>> {
+        if self
+            .keep_alive
+            .as_ref()
+            .map_or(false, KeepAlive::is_timed_out)
+        {
+            return Poll::Ready(Err(proto::Error::KeepAliveTimedOut));
+        }
+
         let reason = if let Some(theirs) = self.error.take() {
             match (ours, theirs) {
                 (Reason::NO_ERROR, err) | (err, Reason::NO_ERROR) => err,
@@ -157,6 +256,10 @@ where
         self.ping_pong.take_user_pings()
     }
 
+    pub(crate) fn take_user_extensions(&mut self) -> Option<UserExtensions> {
+        self.extensions.take_user_extensions()
+    }
+
     pub fn poll(&mut self, cx: &mut Context) -> Poll<Result<(), proto::Error>> {
         let span = self.span.clone();
         let _e = span.enter();
@@ -166,6 +269,12 @@ where
 
         loop {
             tracing::trace!(connection.state = ?self.state);
+            if let State::Open | State::Closing(_) = self.state {
+                if self.poll_keep_alive(cx).is_ready() {
+                    continue;
+                }
+            }
+
             match self.state {
                 State::Open => match self.poll2(cx) {
                     Poll::Ready(Ok(())) => {
@@ -246,13 +355,19 @@ where
             }
             ready!(self.poll_ready(cx))?;
 
-            match ready!(Pin::new(&mut self.codec).poll_next(cx)?) {
+            let frame = ready!(Pin::new(&mut self.codec).poll_next(cx)?);
+            if let Some(keep_alive) = self.keep_alive.as_mut() {
+                keep_alive.recv_frame();
+            }
+
+            match frame {
                 Some(Headers(frame)) => {
                     tracing::trace!(?frame, "recv HEADERS");
                     self.streams.recv_headers(frame)?;
                 }
                 Some(Data(frame)) => {
                     tracing::trace!(?frame, "recv DATA");
+                    self.recv_bdp_data(frame.payload().len());
                     self.streams.recv_data(frame)?;
                 }
                 Some(Reset(frame)) => {
@@ -265,6 +380,7 @@ where
                 }
                 Some(Settings(frame)) => {
                     tracing::trace!(?frame, "recv SETTINGS");
+                    self.extensions.recv_settings(&frame);
                     self.settings
                         .recv_settings(frame, &mut self.codec, &mut self.streams)?;
                 }
@@ -284,6 +400,12 @@ where
 
                         let last_processed_id = self.streams.last_processed_id();
                         self.go_away(last_processed_id, Reason::NO_ERROR);
+                    } else if status.is_bdp() {
+                        self.recv_bdp_pong();
+                    } else if status.is_keep_alive() {
+                        if let Some(keep_alive) = self.keep_alive.as_mut() {
+                            keep_alive.recv_pong();
+                        }
                     }
                 }
                 Some(WindowUpdate(frame)) => {
@@ -293,6 +415,14 @@ where
                 Some(Priority(frame)) => {
                     tracing::trace!(?frame, "recv PRIORITY");
                 }
+                Some(PriorityUpdate(frame)) => {
+                    tracing::trace!(?frame, "recv PRIORITY_UPDATE");
+                    self.streams.recv_priority_update(frame)?;
+                }
+                Some(Extension(frame)) => {
+                    tracing::trace!(?frame, "recv extension frame");
+                    self.extensions.recv_frame(frame);
+                }
                 None => {
                     tracing::trace!("codec closed");
                     self.streams.recv_eof(false).expect("mutex poisoned");
@@ -326,6 +456,23 @@ where
         self.streams.next_incoming()
     }
 
+    pub(crate) fn recv_upgrade(
+        &mut self,
+        settings: &frame::Settings,
+        request: frame::Headers,
+    ) -> Result<(), RecvError> {
+        if let Some(val) = settings.header_table_size() {
+            self.codec.set_send_header_table_size(val as usize);
+        }
+
+        if let Some(val) = settings.max_frame_size() {
+            self.codec.set_max_send_frame_size(val as usize);
+        }
+
+        self.streams.apply_remote_settings(settings)?;
+        self.streams.recv_headers(request)
+    }
+
     pub fn go_away_gracefully(&mut self) {
         if self.go_away.is_going_away() {
             return;
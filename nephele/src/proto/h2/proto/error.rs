@@ -7,6 +7,7 @@ use crate::proto::h2::frame::Reason;
 pub enum Error {
     Proto(Reason),
     Io(io::Error),
+    KeepAliveTimedOut,
 }
 
 impl Error {
@@ -14,8 +15,13 @@ impl Error {
         match *self {
             Error::Proto(reason) => Error::Proto(reason),
             Error::Io(ref io) => Error::Io(io::Error::from(io.kind())),
+            Error::KeepAliveTimedOut => Error::KeepAliveTimedOut,
         }
     }
+
+    fn keep_alive_timed_out() -> io::Error {
+        io::Error::new(io::ErrorKind::TimedOut, "keep-alive timed out")
+    }
 }
 
 impl From<Reason> for Error {
@@ -35,6 +41,7 @@ impl From<Error> for RecvError {
         match src {
             Error::Proto(reason) => RecvError::Connection(reason),
             Error::Io(e) => RecvError::Io(e),
+            Error::KeepAliveTimedOut => RecvError::Io(Error::keep_alive_timed_out()),
         }
     }
 }
@@ -44,6 +51,7 @@ impl From<Error> for SendError {
         match src {
             Error::Proto(reason) => SendError::Connection(reason),
             Error::Io(e) => SendError::Io(e),
+            Error::KeepAliveTimedOut => SendError::Io(Error::keep_alive_timed_out()),
         }
     }
 }
@@ -0,0 +1,160 @@
+use bytes::Buf;
+use cynthia::future::swap::AsyncWrite;
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use crate::proto::h2::codec::Codec;
+use crate::proto::h2::frame::{self, ExtensionFrame};
+use crate::proto::h2::proto;
+
+const MAX_BUFFERED_FRAMES: usize = 64;
+
+#[derive(Debug)]
+pub(crate) struct Extensions {
+    shared: Arc<Mutex<Shared>>,
+    taken: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct UserExtensions(Arc<Mutex<Shared>>);
+
+#[derive(Debug, Default)]
+struct Shared {
+    remote_settings: Vec<(u16, u32)>,
+    received: VecDeque<ExtensionFrame>,
+    pending_send: VecDeque<ExtensionFrame>,
+    recv_task: Option<Waker>,
+    send_task: Option<Waker>,
+    closed: bool,
+}
+
+impl Extensions {
+    pub(crate) fn new() -> Self {
+        Extensions {
+            shared: Arc::new(Mutex::new(Shared::default())),
+            taken: false,
+        }
+    }
+
+    pub(crate) fn take_user_extensions(&mut self) -> Option<UserExtensions> {
+        if self.taken {
+            return None;
+        }
+
+        self.taken = true;
+        Some(UserExtensions(self.shared.clone()))
+    }
+
+    pub(crate) fn recv_settings(&mut self, settings: &frame::Settings) {
+        if settings.extensions().is_empty() {
+            return;
+        }
+
+        let mut shared = self.shared.lock().unwrap();
+        for &(id, val) in settings.extensions() {
+            tracing::trace!("recv extension setting; id={:#x}; val={}", id, val);
+            shared.remote_settings.retain(|(ext, _)| *ext != id);
+            shared.remote_settings.push((id, val));
+        }
+    }
+
+    pub(crate) fn recv_frame(&mut self, frame: ExtensionFrame) {
+        if !self.taken {
+            tracing::trace!("ignoring extension frame; kind={:#x}", frame.kind());
+            return;
+        }
+
+        let mut shared = self.shared.lock().unwrap();
+        if shared.received.len() >= MAX_BUFFERED_FRAMES {
+            tracing::debug!(
+                "dropping extension frame; kind={:#x}; buffered={}",
+                frame.kind(),
+                shared.received.len()
+            );
+            return;
+        }
+
+        shared.received.push_back(frame);
+        if let Some(task) = shared.recv_task.take() {
+            task.wake();
+        }
+    }
+
+    pub(crate) fn send_pending<T, B>(
+        &mut self,
+        cx: &mut Context,
+        dst: &mut Codec<T, B>,
+    ) -> Poll<io::Result<()>>
+    where
+        T: AsyncWrite + Unpin,
+        B: Buf,
+    {
+        let mut shared = self.shared.lock().unwrap();
+        while let Some(frame) = shared.pending_send.pop_front() {
+            if !dst.poll_ready(cx)?.is_ready() {
+                shared.pending_send.push_front(frame);
+                return Poll::Pending;
+            }
+
+            if let Err(e) = dst.buffer(frame.into()) {
+                tracing::debug!("dropping extension frame; err={:?}", e);
+            }
+        }
+
+        shared.send_task = Some(cx.waker().clone());
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Drop for Extensions {
+    fn drop(&mut self) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.closed = true;
+        if let Some(task) = shared.recv_task.take() {
+            task.wake();
+        }
+    }
+}
+
+impl UserExtensions {
+    pub(crate) fn send_frame(&self, frame: ExtensionFrame) -> Result<(), proto::Error> {
+        let mut shared = self.0.lock().unwrap();
+        if shared.closed {
+            return Err(io::Error::from(io::ErrorKind::BrokenPipe).into());
+        }
+
+        shared.pending_send.push_back(frame);
+        if let Some(task) = shared.send_task.take() {
+            task.wake();
+        }
+        Ok(())
+    }
+
+    pub(crate) fn poll_frame(&self, cx: &mut Context) -> Poll<Option<ExtensionFrame>> {
+        let mut shared = self.0.lock().unwrap();
+        if let Some(frame) = shared.received.pop_front() {
+            return Poll::Ready(Some(frame));
+        }
+        if shared.closed {
+            return Poll::Ready(None);
+        }
+
+        shared.recv_task = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    pub(crate) fn remote_setting(&self, id: u16) -> Option<u32> {
+        let shared = self.0.lock().unwrap();
+        shared
+            .remote_settings
+            .iter()
+            .find(|(ext, _)| *ext == id)
+            .map(|(_, val)| *val)
+    }
+
+    pub(crate) fn remote_settings(&self) -> Vec<(u16, u32)> {
+        self.0.lock().unwrap().remote_settings.clone()
+    }
+}
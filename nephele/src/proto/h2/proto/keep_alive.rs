@@ -0,0 +1,90 @@
+use cynthia::future::Future;
+use cynthia::io::Timer;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub(crate) struct KeepAlive {
+    interval: Duration,
+    timeout: Duration,
+    while_idle: bool,
+    state: State,
+    timer: Timer,
+    last_read_at: Instant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Scheduled,
+    PingSent,
+    TimedOut,
+}
+
+#[derive(Debug)]
+pub(crate) enum KeepAliveEvent {
+    SendPing,
+    TimedOut,
+}
+
+impl KeepAlive {
+    pub(crate) fn new(interval: Duration, timeout: Duration, while_idle: bool) -> Self {
+        KeepAlive {
+            interval,
+            timeout,
+            while_idle,
+            state: State::Scheduled,
+            timer: Timer::after(interval),
+            last_read_at: Instant::now(),
+        }
+    }
+
+    pub(crate) fn recv_frame(&mut self) {
+        self.last_read_at = Instant::now();
+    }
+
+    pub(crate) fn recv_pong(&mut self) {
+        if self.state == State::PingSent {
+            tracing::trace!("recv keep-alive pong");
+            self.state = State::Scheduled;
+            self.timer.set_after(self.interval);
+        }
+    }
+
+    pub(crate) fn is_timed_out(&self) -> bool {
+        self.state == State::TimedOut
+    }
+
+    pub(crate) fn poll(&mut self, cx: &mut Context, is_idle: bool) -> Poll<KeepAliveEvent> {
+        match self.state {
+            State::Scheduled => {
+                if is_idle && !self.while_idle {
+                    return Poll::Pending;
+                }
+
+                loop {
+                    ready!(Pin::new(&mut self.timer).poll(cx));
+
+                    let deadline = self.last_read_at + self.interval;
+                    if deadline <= Instant::now() {
+                        break;
+                    }
+                    self.timer.set_at(deadline);
+                }
+
+                tracing::trace!("keep-alive interval elapsed; sending ping");
+                self.state = State::PingSent;
+                self.timer.set_after(self.timeout);
+                Poll::Ready(KeepAliveEvent::SendPing)
+            }
+            State::PingSent => {
+                ready!(Pin::new(&mut self.timer).poll(cx));
+
+                tracing::debug!("keep-alive ping timed out after {:?}", self.timeout);
+                self.state = State::TimedOut;
+                Poll::Ready(KeepAliveEvent::TimedOut)
+            }
+            State::TimedOut => Poll::Pending,
+        }
+    }
+}
@@ -1,6 +1,9 @@
+mod bdp;
 mod connection;
 mod error;
+mod extensions;
 mod go_away;
+mod keep_alive;
 mod peer;
 mod ping_pong;
 mod settings;
@@ -8,6 +11,7 @@ mod streams;
 
 pub(crate) use crate::proto::h2::proto::connection::{Config, Connection};
 pub(crate) use crate::proto::h2::proto::error::Error;
+pub(crate) use crate::proto::h2::proto::extensions::UserExtensions;
 pub(crate) use crate::proto::h2::proto::peer::{Dyn as DynPeer, Peer};
 pub(crate) use crate::proto::h2::proto::ping_pong::UserPings;
 pub(crate) use crate::proto::h2::proto::streams::{OpaqueStreamRef, StreamRef, Streams};
@@ -15,7 +19,10 @@ pub(crate) use crate::proto::h2::proto::streams::{Open, PollReset, Prioritized};
 
 use crate::proto::h2::codec::Codec;
 
+use crate::proto::h2::proto::bdp::Bdp;
+use crate::proto::h2::proto::extensions::Extensions;
 use crate::proto::h2::proto::go_away::GoAway;
+use crate::proto::h2::proto::keep_alive::{KeepAlive, KeepAliveEvent};
 use crate::proto::h2::proto::ping_pong::PingPong;
 use crate::proto::h2::proto::settings::Settings;
 
@@ -33,3 +40,4 @@ pub type WindowSize = u32;
 pub const MAX_WINDOW_SIZE: WindowSize = (1 << 31) - 1;
 pub const DEFAULT_RESET_STREAM_MAX: usize = 10;
 pub const DEFAULT_RESET_STREAM_SECS: u64 = 30;
+pub const DEFAULT_KEEP_ALIVE_TIMEOUT_SECS: u64 = 20;
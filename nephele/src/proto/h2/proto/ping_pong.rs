@@ -15,6 +15,8 @@ pub(crate) struct PingPong {
     pending_ping: Option<PendingPing>,
     pending_pong: Option<PingPayload>,
     user_pings: Option<UserPingsRx>,
+    bdp_ping: InternalPing,
+    keep_alive_ping: InternalPing,
 }
 
 #[derive(Debug)]
@@ -36,11 +38,20 @@ struct PendingPing {
     sent: bool,
 }
 
+#[derive(Debug, PartialEq, Eq)]
+enum InternalPing {
+    Idle,
+    Pending,
+    Sent,
+}
+
 #[derive(Debug)]
 pub(crate) enum ReceivedPing {
     MustAck,
     Unknown,
     Shutdown,
+    Bdp,
+    KeepAlive,
 }
 
 const USER_STATE_EMPTY: usize = 0;
@@ -55,6 +66,8 @@ impl PingPong {
             pending_ping: None,
             pending_pong: None,
             user_pings: None,
+            bdp_ping: InternalPing::Idle,
+            keep_alive_ping: InternalPing::Idle,
         }
     }
 
@@ -81,6 +94,18 @@ impl PingPong {
         });
     }
 
+    pub(crate) fn ping_bdp(&mut self) {
+        if self.bdp_ping == InternalPing::Idle {
+            self.bdp_ping = InternalPing::Pending;
+        }
+    }
+
+    pub(crate) fn ping_keep_alive(&mut self) {
+        if self.keep_alive_ping == InternalPing::Idle {
+            self.keep_alive_ping = InternalPing::Pending;
+        }
+    }
+
     pub(crate) fn recv_ping(&mut self, ping: Ping) -> ReceivedPing {
         assert!(self.pending_pong.is_none());
 
@@ -99,6 +124,18 @@ impl PingPong {
                 self.pending_ping = Some(pending);
             }
 
+            if ping.payload() == &Ping::BDP && self.bdp_ping == InternalPing::Sent {
+                tracing::trace!("recv PING BDP ack");
+                self.bdp_ping = InternalPing::Idle;
+                return ReceivedPing::Bdp;
+            }
+
+            if ping.payload() == &Ping::KEEP_ALIVE && self.keep_alive_ping == InternalPing::Sent {
+                tracing::trace!("recv PING KEEP_ALIVE ack");
+                self.keep_alive_ping = InternalPing::Idle;
+                return ReceivedPing::KeepAlive;
+            }
+
             if let Some(ref users) = self.user_pings {
                 if ping.payload() == &Ping::USER && users.receive_pong() {
                     tracing::trace!("recv PING USER ack");
@@ -145,6 +182,26 @@ impl PingPong {
         T: AsyncWrite + Unpin,
         B: Buf,
     {
+        if self.bdp_ping == InternalPing::Pending {
+            if !dst.poll_ready(cx)?.is_ready() {
+                return Poll::Pending;
+            }
+
+            dst.buffer(Ping::new(Ping::BDP).into())
+                .expect("invalid ping frame");
+            self.bdp_ping = InternalPing::Sent;
+        }
+
+        if self.keep_alive_ping == InternalPing::Pending {
+            if !dst.poll_ready(cx)?.is_ready() {
+                return Poll::Pending;
+            }
+
+            dst.buffer(Ping::new(Ping::KEEP_ALIVE).into())
+                .expect("invalid ping frame");
+            self.keep_alive_ping = InternalPing::Sent;
+        }
+
         if let Some(ref mut ping) = self.pending_ping {
             if !ping.sent {
                 if !dst.poll_ready(cx)?.is_ready() {
@@ -183,6 +240,20 @@ impl ReceivedPing {
             _ => false,
         }
     }
+
+    pub(crate) fn is_bdp(&self) -> bool {
+        match *self {
+            ReceivedPing::Bdp => true,
+            _ => false,
+        }
+    }
+
+    pub(crate) fn is_keep_alive(&self) -> bool {
+        match *self {
+            ReceivedPing::KeepAlive => true,
+            _ => false,
+        }
+    }
 }
 
 impl UserPings {
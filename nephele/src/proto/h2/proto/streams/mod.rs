@@ -36,6 +36,7 @@ pub struct Config {
     pub initial_max_send_streams: usize,
     pub local_next_stream_id: StreamId,
     pub local_push_enabled: bool,
+    pub extended_connect_protocol_enabled: bool,
     pub local_reset_duration: Duration,
     pub local_reset_max: usize,
     pub remote_init_window_sz: WindowSize,
@@ -7,19 +7,27 @@ use std::{cmp, fmt, mem};
 use super::*;
 use crate::proto::h2::codec::UserError;
 use crate::proto::h2::codec::UserError::*;
+use crate::proto::h2::ext::Priority;
 use crate::proto::h2::frame::{Reason, StreamId};
 use crate::proto::h2::proto::streams::store::Resolve;
 
+const URGENCY_LEVELS: usize = Priority::MAX_URGENCY as usize + 1;
+
 #[derive(Debug)]
 pub(super) struct Prioritize {
-    pending_send: store::Queue<stream::NextSend>,
-    pending_capacity: store::Queue<stream::NextSendCapacity>,
+    pending_send: PriorityQueue<stream::NextSend>,
+    pending_capacity: PriorityQueue<stream::NextSendCapacity>,
     pending_open: store::Queue<stream::NextOpen>,
     flow: FlowControl,
     last_opened_id: StreamId,
     in_flight_data_frame: InFlightData,
 }
 
+#[derive(Debug)]
+struct PriorityQueue<N> {
+    queues: [store::Queue<N>; URGENCY_LEVELS],
+}
+
 #[derive(Debug, Eq, PartialEq)]
 enum InFlightData {
     Nothing,
@@ -45,8 +53,8 @@ impl Prioritize {
         tracing::trace!("Prioritize::new; flow={:?}", flow);
 
         Prioritize {
-            pending_send: store::Queue::new(),
-            pending_capacity: store::Queue::new(),
+            pending_send: PriorityQueue::new(),
+            pending_capacity: PriorityQueue::new(),
             pending_open: store::Queue::new(),
             flow,
             last_opened_id: StreamId::ZERO,
@@ -78,6 +86,27 @@ impl Prioritize {
         }
     }
 
+    pub fn set_priority(&mut self, stream: &mut store::Ptr, priority: Priority) {
+        tracing::trace!(?stream.id, ?priority, "set_priority");
+
+        if stream.priority.urgency() == priority.urgency() {
+            stream.priority = priority;
+            return;
+        }
+
+        let is_pending_send = self.pending_send.remove(stream);
+        let is_pending_capacity = self.pending_capacity.remove(stream);
+
+        stream.priority = priority;
+
+        if is_pending_send {
+            self.pending_send.push(stream);
+        }
+        if is_pending_capacity {
+            self.pending_capacity.push(stream);
+        }
+    }
+
     pub fn queue_open(&mut self, stream: &mut store::Ptr) {
         self.pending_open.push(stream);
     }
@@ -444,7 +473,7 @@ impl Prioritize {
 
         if stream.send_flow.available() > 0 {
             debug_assert!(!stream.pending_send.is_empty());
-            self.pending_send.push(stream);
+            self.pending_send.requeue(stream);
         }
     }
 
@@ -605,7 +634,7 @@ impl Prioritize {
                     }
 
                     if !stream.pending_send.is_empty() || stream.state.is_scheduled_reset() {
-                        self.pending_send.push(&mut stream);
+                        self.pending_send.requeue(&mut stream);
                     }
 
                     counts.transition_after(stream, is_pending_reset);
@@ -633,6 +662,42 @@ impl Prioritize {
     }
 }
 
+impl<N> PriorityQueue<N>
+where
+    N: store::Next,
+{
+    fn new() -> Self {
+        PriorityQueue {
+            queues: [(); URGENCY_LEVELS].map(|_| store::Queue::new()),
+        }
+    }
+
+    fn push(&mut self, stream: &mut store::Ptr) {
+        self.queues[stream.priority.urgency() as usize].push(stream);
+    }
+
+    fn requeue(&mut self, stream: &mut store::Ptr) {
+        let queue = &mut self.queues[stream.priority.urgency() as usize];
+        if stream.priority.is_incremental() {
+            queue.push(stream);
+        } else {
+            queue.push_front(stream);
+        }
+    }
+
+    fn remove(&mut self, stream: &mut store::Ptr) -> bool {
+        self.queues[stream.priority.urgency() as usize].remove(stream)
+    }
+
+    fn pop<'a, R>(&mut self, store: &'a mut R) -> Option<store::Ptr<'a>>
+    where
+        R: Resolve,
+    {
+        let urgency = self.queues.iter().position(|queue| !queue.is_empty())?;
+        self.queues[urgency].pop(store)
+    }
+}
+
 impl<B> Buf for Prioritized<B>
 where
     B: Buf,
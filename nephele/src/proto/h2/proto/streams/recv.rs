@@ -1,5 +1,6 @@
 use cynthia::future::swap::AsyncWrite;
 use http::{HeaderMap, Request, Response};
+use std::collections::VecDeque;
 use std::io;
 use std::task::Context;
 use std::task::{Poll, Waker};
@@ -7,9 +8,12 @@ use std::time::{Duration, Instant};
 
 use super::*;
 use crate::proto::h2::codec::{RecvError, UserError};
+use crate::proto::h2::ext::Priority;
 use crate::proto::h2::frame::{PushPromiseHeaderError, Reason, DEFAULT_INITIAL_WINDOW_SIZE};
 use crate::proto::h2::{frame, proto};
 
+const MAX_PENDING_PRIORITY_UPDATES: usize = 32;
+
 #[derive(Debug)]
 pub(super) struct Recv {
     init_window_sz: WindowSize,
@@ -25,6 +29,8 @@ pub(super) struct Recv {
     buffer: Buffer<Event>,
     refused: Option<StreamId>,
     is_push_enabled: bool,
+    is_extended_connect_protocol_enabled: bool,
+    pending_priority_updates: VecDeque<(StreamId, Priority)>,
 }
 
 #[derive(Debug)]
@@ -76,6 +82,8 @@ impl Recv {
             buffer: Buffer::new(),
             refused: None,
             is_push_enabled: config.local_push_enabled,
+            is_extended_connect_protocol_enabled: config.extended_connect_protocol_enabled,
+            pending_priority_updates: VecDeque::new(),
         }
     }
 
@@ -128,6 +136,20 @@ impl Recv {
             }
 
             counts.inc_num_recv_streams(stream);
+
+            if counts.peer().is_server() {
+                if let Some(value) = frame.fields().get("priority") {
+                    stream.priority = Priority::parse(value.as_bytes());
+                }
+                if let Some(pos) = self
+                    .pending_priority_updates
+                    .iter()
+                    .position(|(id, _)| *id == stream.id)
+                {
+                    stream.priority = self.pending_priority_updates[pos].1;
+                    self.pending_priority_updates.remove(pos);
+                }
+            }
         }
 
         if !stream.content_length.is_head() {
@@ -172,6 +194,16 @@ impl Recv {
 
         let stream_id = frame.stream_id();
         let (pseudo, fields) = frame.into_parts();
+
+        if pseudo.protocol.is_some() && !self.is_extended_connect_protocol_enabled {
+            proto_err!(stream: "cannot use :protocol if extended connect protocol is disabled; stream={:?}", stream.id);
+            return Err(RecvError::Stream {
+                id: stream.id,
+                reason: Reason::PROTOCOL_ERROR,
+            }
+            .into());
+        }
+
         let message = counts
             .peer()
             .convert_poll_message(pseudo, fields, stream_id)?;
@@ -580,6 +612,23 @@ impl Recv {
         Ok(())
     }
 
+    pub fn recv_priority_update(&mut self, id: StreamId, priority: Priority) {
+        match self.next_stream_id {
+            Ok(next_id) if id >= next_id => {}
+            _ => {
+                tracing::trace!("ignoring PRIORITY_UPDATE for closed stream; id={:?}", id);
+                return;
+            }
+        }
+
+        self.pending_priority_updates
+            .retain(|(pending, _)| *pending != id);
+        if self.pending_priority_updates.len() >= MAX_PENDING_PRIORITY_UPDATES {
+            self.pending_priority_updates.pop_front();
+        }
+        self.pending_priority_updates.push_back((id, priority));
+    }
+
     pub fn recv_reset(&mut self, frame: frame::Reset, stream: &mut Stream) {
         stream
             .state
@@ -1,6 +1,7 @@
 use bytes::Buf;
 use cynthia::future::swap::AsyncWrite;
 use http;
+use std::collections::VecDeque;
 use std::io;
 use std::task::{Context, Poll, Waker};
 
@@ -10,6 +11,7 @@ use super::{
 };
 
 use crate::proto::h2::codec::{RecvError, UserError};
+use crate::proto::h2::ext::Priority;
 use crate::proto::h2::frame::{self, Reason};
 
 #[derive(Debug)]
@@ -19,6 +21,8 @@ pub(super) struct Send {
     init_window_sz: WindowSize,
     prioritize: Prioritize,
     is_push_enabled: bool,
+    is_extended_connect_protocol_enabled: bool,
+    pending_priority_updates: VecDeque<frame::PriorityUpdate>,
 }
 
 #[derive(Debug)]
@@ -35,6 +39,8 @@ impl Send {
             next_stream_id: Ok(config.local_next_stream_id),
             prioritize: Prioritize::new(config),
             is_push_enabled: true,
+            is_extended_connect_protocol_enabled: false,
+            pending_priority_updates: VecDeque::new(),
         }
     }
 
@@ -42,6 +48,10 @@ impl Send {
         self.init_window_sz
     }
 
+    pub fn is_extended_connect_protocol_enabled(&self) -> bool {
+        self.is_extended_connect_protocol_enabled
+    }
+
     pub fn open(&mut self) -> Result<StreamId, UserError> {
         let stream_id = self.ensure_next_stream_id()?;
         self.next_stream_id = stream_id.next_id();
@@ -275,6 +285,39 @@ impl Send {
         self.prioritize.reserve_capacity(capacity, stream, counts)
     }
 
+    pub fn set_priority(&mut self, stream: &mut store::Ptr, priority: Priority) {
+        self.prioritize.set_priority(stream, priority)
+    }
+
+    pub fn queue_priority_update(&mut self, stream_id: StreamId, priority: Priority) {
+        self.pending_priority_updates
+            .retain(|frame| frame.prioritized_id() != stream_id);
+        self.pending_priority_updates
+            .push_back(frame::PriorityUpdate::new(stream_id, priority));
+    }
+
+    pub fn send_pending_priority_updates<T, B>(
+        &mut self,
+        cx: &mut Context,
+        dst: &mut Codec<T, Prioritized<B>>,
+    ) -> Poll<io::Result<()>>
+    where
+        T: AsyncWrite + Unpin,
+        B: Buf,
+    {
+        while let Some(frame) = self.pending_priority_updates.pop_front() {
+            if !dst.poll_ready(cx)?.is_ready() {
+                self.pending_priority_updates.push_front(frame);
+                return Poll::Pending;
+            }
+
+            dst.buffer(frame.into())
+                .expect("invalid PRIORITY_UPDATE frame");
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
     pub fn poll_capacity(
         &mut self,
         cx: &Context,
@@ -432,6 +475,10 @@ impl Send {
             self.is_push_enabled = val
         }
 
+        if let Some(val) = settings.is_extended_connect_protocol_enabled() {
+            self.is_extended_connect_protocol_enabled = val;
+        }
+
         Ok(())
     }
 
@@ -36,6 +36,7 @@ enum Cause {
     Proto(Reason),
     LocallyReset(Reason),
     Io,
+    KeepAliveTimedOut,
     Scheduled(Reason),
 }
 
@@ -198,6 +199,7 @@ impl State {
                 self.inner = Closed(match *err {
                     Proto(reason) => Cause::LocallyReset(reason),
                     Io(..) => Cause::Io,
+                    KeepAliveTimedOut => Cause::KeepAliveTimedOut,
                 });
             }
         }
@@ -333,6 +335,7 @@ impl State {
             | Closed(Cause::LocallyReset(reason))
             | Closed(Cause::Scheduled(reason)) => Err(proto::Error::Proto(reason)),
             Closed(Cause::Io) => Err(proto::Error::Io(io::ErrorKind::BrokenPipe.into())),
+            Closed(Cause::KeepAliveTimedOut) => Err(proto::Error::KeepAliveTimedOut),
             Closed(Cause::EndStream) | HalfClosedRemote(..) | ReservedLocal => Ok(false),
             _ => Ok(true),
         }
@@ -347,6 +350,7 @@ impl State {
             | Closed(Cause::LocallyReset(reason))
             | Closed(Cause::Scheduled(reason)) => Ok(Some(reason)),
             Closed(Cause::Io) => Err(proto::Error::Io(io::ErrorKind::BrokenPipe.into()).into()),
+            Closed(Cause::KeepAliveTimedOut) => Err(proto::Error::KeepAliveTimedOut.into()),
             Open {
                 local: Streaming, ..
             }
@@ -212,6 +212,10 @@ where
         }
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_none()
+    }
+
     pub fn take(&mut self) -> Self {
         Queue {
             indices: self.indices.take(),
@@ -252,6 +256,71 @@ where
         true
     }
 
+    pub fn push_front(&mut self, stream: &mut store::Ptr) -> bool {
+        tracing::trace!("Queue::push_front");
+
+        if N::is_queued(stream) {
+            tracing::trace!(" -> already queued");
+            return false;
+        }
+
+        N::set_queued(stream, true);
+
+        debug_assert!(N::next(stream).is_none());
+
+        match self.indices {
+            Some(ref mut idxs) => {
+                N::set_next(stream, Some(idxs.head));
+                idxs.head = stream.key();
+            }
+            None => {
+                self.indices = Some(store::Indices {
+                    head: stream.key(),
+                    tail: stream.key(),
+                });
+            }
+        }
+
+        true
+    }
+
+    pub fn remove(&mut self, stream: &mut store::Ptr) -> bool {
+        let mut idxs = match self.indices {
+            Some(idxs) if N::is_queued(stream) => idxs,
+            _ => return false,
+        };
+        let key = stream.key();
+
+        if idxs.head == key {
+            match N::take_next(stream) {
+                Some(next) => {
+                    idxs.head = next;
+                    self.indices = Some(idxs);
+                }
+                None => self.indices = None,
+            }
+        } else {
+            let mut prev = idxs.head;
+            loop {
+                match N::next(&stream.resolve(prev)) {
+                    Some(next) if next == key => break,
+                    Some(next) => prev = next,
+                    None => return false,
+                }
+            }
+
+            let next = N::take_next(stream);
+            N::set_next(&mut stream.resolve(prev), next);
+            if idxs.tail == key {
+                idxs.tail = prev;
+                self.indices = Some(idxs);
+            }
+        }
+
+        N::set_queued(stream, false);
+        true
+    }
+
     pub fn pop<'a, R>(&mut self, store: &'a mut R) -> Option<store::Ptr<'a>>
     where
         R: Resolve,
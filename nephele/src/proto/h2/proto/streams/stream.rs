@@ -3,6 +3,7 @@ use std::time::Instant;
 use std::usize;
 
 use super::*;
+use crate::proto::h2::ext::Priority;
 
 #[derive(Debug)]
 pub(super) struct Stream {
@@ -12,6 +13,7 @@ pub(super) struct Stream {
     pub ref_count: usize,
     pub next_pending_send: Option<store::Key>,
     pub is_pending_send: bool,
+    pub priority: Priority,
     pub send_flow: FlowControl,
     pub requested_send_capacity: WindowSize,
     pub buffered_send_data: WindowSize,
@@ -84,6 +86,7 @@ impl Stream {
 
             next_pending_send: None,
             is_pending_send: false,
+            priority: Priority::default(),
             send_flow,
             requested_send_capacity: 0,
             buffered_send_data: 0,
@@ -6,6 +6,7 @@ use std::task::{Context, Poll, Waker};
 use std::{fmt, io};
 
 use crate::proto::h2::codec::{Codec, RecvError, SendError, UserError};
+use crate::proto::h2::ext::{Priority, Protocol};
 use crate::proto::h2::frame::{self, Frame, Reason};
 use crate::proto::h2::proto::streams::recv::RecvHeaderBlockError;
 use crate::proto::h2::proto::streams::store::{self, Entry, Resolve, Store};
@@ -393,6 +394,29 @@ where
         Ok(())
     }
 
+    pub fn recv_priority_update(&mut self, frame: frame::PriorityUpdate) -> Result<(), RecvError> {
+        let id = frame.prioritized_id();
+        let mut me = self.inner.lock().unwrap();
+        let me = &mut *me;
+
+        if !P::is_server() {
+            proto_err!(conn: "recv_priority_update: client received PRIORITY_UPDATE");
+            return Err(RecvError::Connection(Reason::PROTOCOL_ERROR));
+        }
+
+        if id.is_server_initiated() {
+            tracing::trace!("ignoring PRIORITY_UPDATE for pushed stream; id={:?}", id);
+            return Ok(());
+        }
+
+        match me.store.find_mut(&id) {
+            Some(mut stream) => me.actions.send.set_priority(&mut stream, frame.priority()),
+            None => me.actions.recv.recv_priority_update(id, frame.priority()),
+        }
+
+        Ok(())
+    }
+
     pub fn recv_push_promise(&mut self, frame: frame::PushPromise) -> Result<(), RecvError> {
         let mut me = self.inner.lock().unwrap();
         let me = &mut *me;
@@ -504,6 +528,19 @@ where
         me.actions.recv.send_pending_refusal(cx, dst)
     }
 
+    pub fn send_pending_priority_updates<T>(
+        &mut self,
+        cx: &mut Context,
+        dst: &mut Codec<T, Prioritized<B>>,
+    ) -> Poll<io::Result<()>>
+    where
+        T: AsyncWrite + Unpin,
+    {
+        let mut me = self.inner.lock().unwrap();
+        let me = &mut *me;
+        me.actions.send.send_pending_priority_updates(cx, dst)
+    }
+
     pub fn clear_expired_reset_streams(&mut self) {
         let mut me = self.inner.lock().unwrap();
         let me = &mut *me;
@@ -594,6 +631,12 @@ where
             return Err(UserError::UnexpectedFrameType.into());
         }
 
+        if request.extensions().get::<Protocol>().is_some()
+            && !me.actions.send.is_extended_connect_protocol_enabled()
+        {
+            return Err(UserError::ExtendedConnectProtocolDisabled.into());
+        }
+
         let stream_id = me.actions.send.open()?;
 
         let mut stream = Stream::new(
@@ -634,6 +677,48 @@ where
         })
     }
 
+    pub fn send_upgrade(&mut self, head: bool) -> Result<StreamRef<B>, SendError> {
+        use super::stream::ContentLength;
+
+        let mut me = self.inner.lock().unwrap();
+        let me = &mut *me;
+
+        me.actions.ensure_no_conn_error()?;
+
+        if me.counts.peer().is_server() {
+            return Err(UserError::UnexpectedFrameType.into());
+        }
+
+        let stream_id = me.actions.send.open()?;
+        if stream_id != StreamId::from(1) {
+            return Err(UserError::Rejected.into());
+        }
+
+        let mut stream = Stream::new(
+            stream_id,
+            me.actions.send.init_window_sz(),
+            me.actions.recv.init_window_sz(),
+        );
+
+        if head {
+            stream.content_length = ContentLength::Head;
+        }
+
+        stream.state.send_open(true)?;
+
+        let mut stream = me.store.insert(stream.id, stream);
+        if me.counts.can_inc_num_send_streams() {
+            me.counts.inc_num_send_streams(&mut stream);
+        }
+
+        me.refs += 1;
+
+        Ok(StreamRef {
+            opaque: OpaqueStreamRef::new(self.inner.clone(), &mut stream),
+            send_buffer: self.send_buffer.clone(),
+        })
+    }
+
     pub fn send_reset(&mut self, id: StreamId, reason: Reason) {
         let mut me = self.inner.lock().unwrap();
         let me = &mut *me;
@@ -734,6 +819,11 @@ where
         me.counts.has_streams()
     }
 
+    pub fn is_extended_connect_protocol_enabled(&self) -> bool {
+        let me = self.inner.lock().unwrap();
+        me.actions.send.is_extended_connect_protocol_enabled()
+    }
+
     pub fn has_streams_or_other_references(&self) -> bool {
         let me = self.inner.lock().unwrap();
         me.counts.has_streams() || me.refs > 1
@@ -923,6 +1013,22 @@ impl<B> StreamRef<B> {
             .reserve_capacity(capacity, &mut stream, &mut me.counts)
     }
 
+    pub fn set_priority(&mut self, priority: Priority) {
+        let mut me = self.opaque.inner.lock().unwrap();
+        let me = &mut *me;
+
+        let mut stream = me.store.resolve(self.opaque.key);
+
+        me.actions.send.set_priority(&mut stream, priority);
+
+        if !me.counts.peer().is_server() && !stream.state.is_closed() {
+            me.actions.send.queue_priority_update(stream.id, priority);
+            if let Some(task) = me.actions.task.take() {
+                task.wake();
+            }
+        }
+    }
+
     pub fn capacity(&self) -> WindowSize {
         let mut me = self.opaque.inner.lock().unwrap();
         let me = &mut *me;
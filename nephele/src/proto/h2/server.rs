@@ -9,11 +9,12 @@ use std::{convert, fmt, io, mem};
 use tracing_futures::{Instrument, Instrumented};
 
 use crate::proto::h2::codec::{Codec, RecvError, UserError};
+use crate::proto::h2::ext::Priority;
 use crate::proto::h2::frame::{
     self, Pseudo, PushPromise, PushPromiseHeaderError, Reason, Settings, StreamId,
 };
 use crate::proto::h2::proto::{self, Config, Prioritized};
-use crate::proto::h2::{FlowControl, PingPong, RecvStream, SendStream};
+use crate::proto::h2::{Extensions, FlowControl, PingPong, RecvStream, SendStream};
 
 #[must_use = "do nothing until polled"]
 pub struct Handshake<T, B: Buf = Bytes> {
@@ -33,6 +34,10 @@ pub struct Builder {
     reset_stream_max: usize,
     settings: Settings,
     initial_target_connection_window_size: Option<u32>,
+    adaptive_window: bool,
+    keep_alive_interval: Option<Duration>,
+    keep_alive_timeout: Duration,
+    keep_alive_while_idle: bool,
 }
 
 #[derive(Debug)]
@@ -68,7 +73,7 @@ struct ReadPreface<T, B> {
 #[derive(Debug)]
 pub(crate) struct Peer;
 
-const PREFACE: [u8; 24] = *b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+pub(crate) const PREFACE: [u8; 24] = *b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
 
 pub fn handshake<T>(io: T) -> Handshake<T, Bytes>
 where
@@ -77,6 +82,31 @@ where
     Builder::new().handshake(io)
 }
 
+pub(crate) async fn handshake_h2c<T>(
+    builder: &Builder,
+    io: T,
+    settings: &Settings,
+    request: Request<()>,
+) -> Result<Connection<T, Bytes>, crate::proto::h2::Error>
+where
+    T: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut connection = builder.handshake(io).await?;
+
+    let (head, _) = request.into_parts();
+    let pseudo = Pseudo::request(head.method, head.uri, None);
+    let mut frame = frame::Headers::new(StreamId::from(1), pseudo, head.headers);
+    frame.set_end_stream();
+
+    match connection.connection.recv_upgrade(settings, frame) {
+        Ok(()) => Ok(connection),
+        Err(RecvError::Connection(reason)) | Err(RecvError::Stream { reason, .. }) => {
+            Err(reason.into())
+        }
+        Err(RecvError::Io(e)) => Err(crate::proto::h2::Error::from_io(e)),
+    }
+}
+
 impl<T, B> Connection<T, B>
 where
     T: AsyncRead + AsyncWrite + Unpin,
@@ -150,6 +180,10 @@ where
         Ok(())
     }
 
+    pub fn bdp_estimate(&self) -> Option<u32> {
+        self.connection.bdp_estimate()
+    }
+
     pub fn poll_closed(&mut self, cx: &mut Context) -> Poll<Result<(), crate::proto::h2::Error>> {
         self.connection.poll(cx).map_err(Into::into)
     }
@@ -168,6 +202,10 @@ where
         self.connection.go_away_gracefully();
     }
 
+    pub fn extensions(&mut self) -> Option<Extensions> {
+        self.connection.take_user_extensions().map(Extensions::new)
+    }
+
     pub fn ping_pong(&mut self) -> Option<PingPong> {
         self.connection.take_user_pings().map(PingPong::new)
     }
@@ -205,6 +243,10 @@ impl Builder {
             reset_stream_max: proto::DEFAULT_RESET_STREAM_MAX,
             settings: Settings::default(),
             initial_target_connection_window_size: None,
+            adaptive_window: false,
+            keep_alive_interval: None,
+            keep_alive_timeout: Duration::from_secs(proto::DEFAULT_KEEP_ALIVE_TIMEOUT_SECS),
+            keep_alive_while_idle: false,
         }
     }
 
@@ -218,6 +260,26 @@ impl Builder {
         self
     }
 
+    pub fn adaptive_window(&mut self, enabled: bool) -> &mut Self {
+        self.adaptive_window = enabled;
+        self
+    }
+
+    pub fn keep_alive_interval(&mut self, interval: Duration) -> &mut Self {
+        self.keep_alive_interval = Some(interval);
+        self
+    }
+
+    pub fn keep_alive_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.keep_alive_timeout = timeout;
+        self
+    }
+
+    pub fn keep_alive_while_idle(&mut self, enabled: bool) -> &mut Self {
+        self.keep_alive_while_idle = enabled;
+        self
+    }
+
     pub fn max_frame_size(&mut self, max: u32) -> &mut Self {
         self.settings.set_max_frame_size(Some(max));
         self
@@ -243,6 +305,16 @@ impl Builder {
         self
     }
 
+    pub fn enable_connect_protocol(&mut self) -> &mut Self {
+        self.settings.set_enable_connect_protocol(Some(1));
+        self
+    }
+
+    pub fn setting(&mut self, id: u16, value: u32) -> &mut Self {
+        self.settings.set_extension(id, Some(value));
+        self
+    }
+
     pub fn handshake<T, B>(&self, io: T) -> Handshake<T, B>
     where
         T: AsyncRead + AsyncWrite + Unpin,
@@ -282,6 +354,10 @@ impl<B: Buf> SendResponse<B> {
             .map_err(Into::into)
     }
 
+    pub fn set_priority(&mut self, priority: Priority) {
+        self.inner.set_priority(priority)
+    }
+
     pub fn send_reset(&mut self, reason: Reason) {
         self.inner.send_reset(reason)
     }
@@ -307,6 +383,10 @@ impl<B: Buf> SendPushedResponse<B> {
         self.inner.send_response(response, end_of_stream)
     }
 
+    pub fn set_priority(&mut self, priority: Priority) {
+        self.inner.set_priority(priority)
+    }
+
     pub fn send_reset(&mut self, reason: Reason) {
         self.inner.send_reset(reason)
     }
@@ -367,7 +447,7 @@ where
         let mut rem = PREFACE.len() - self.pos;
 
         while rem > 0 {
-            let nn = ready!(Pin::new(self.inner_mut()).poll_read(cx, &mut buf))
+            let nn = ready!(Pin::new(self.inner_mut()).poll_read(cx, &mut buf[..rem]))
                 .map_err(crate::proto::h2::Error::from_io)?;
             let n = nn;
             if n == 0 {
@@ -377,7 +457,7 @@ where
                 ))));
             }
 
-            if &PREFACE[self.pos..self.pos + n] != &buf {
+            if PREFACE[self.pos..self.pos + n] != buf[..n] {
                 proto_err!(conn: "read_preface: invalid preface");
                 return Poll::Ready(Err(Reason::PROTOCOL_ERROR.into()));
             }
@@ -431,6 +511,10 @@ where
                     initial_max_send_streams: 0,
                     reset_stream_duration: self.builder.reset_stream_duration,
                     reset_stream_max: self.builder.reset_stream_max,
+                    adaptive_window: self.builder.adaptive_window,
+                    keep_alive_interval: self.builder.keep_alive_interval,
+                    keep_alive_timeout: self.builder.keep_alive_timeout,
+                    keep_alive_while_idle: self.builder.keep_alive_while_idle,
                     settings: self.builder.settings.clone(),
                 },
             );
@@ -515,7 +599,7 @@ impl Peer {
             _,
         ) = request.into_parts();
 
-        let pseudo = Pseudo::request(method, uri);
+        let pseudo = Pseudo::request(method, uri, None);
 
         Ok(PushPromise::new(stream_id, promised_id, pseudo, headers))
     }
@@ -568,6 +652,14 @@ impl proto::Peer for Peer {
             return Err(RecvError::Connection(Reason::PROTOCOL_ERROR));
         }
 
+        let has_protocol = pseudo.protocol.is_some();
+        if let Some(protocol) = pseudo.protocol {
+            if !is_connect {
+                malformed!("malformed headers: :protocol without CONNECT");
+            }
+            b = b.extension(protocol);
+        }
+
         let mut parts = uri::Parts::default();
 
         if let Some(authority) = pseudo.authority {
@@ -582,7 +674,7 @@ impl proto::Peer for Peer {
         }
 
         if let Some(scheme) = pseudo.scheme {
-            if is_connect {
+            if is_connect && !has_protocol {
                 malformed!(":scheme in CONNECT");
             }
             let maybe_scheme = scheme.parse();
@@ -597,12 +689,12 @@ impl proto::Peer for Peer {
             if parts.authority.is_some() {
                 parts.scheme = Some(scheme);
             }
-        } else if !is_connect {
+        } else if !is_connect || has_protocol {
             malformed!("malformed headers: missing scheme");
         }
 
         if let Some(path) = pseudo.path {
-            if is_connect {
+            if is_connect && !has_protocol {
                 malformed!(":path in CONNECT");
             }
 
@@ -614,6 +706,8 @@ impl proto::Peer for Peer {
             parts.path_and_query = Some(maybe_path.or_else(|why| {
                 malformed!("malformed headers: malformed path ({:?}): {}", path, why,)
             })?);
+        } else if has_protocol {
+            malformed!("malformed headers: missing path in extended CONNECT");
         }
 
         b = b.uri(parts);
@@ -6,7 +6,8 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use crate::proto::h2::codec::UserError;
-use crate::proto::h2::frame::Reason;
+use crate::proto::h2::ext::Priority;
+use crate::proto::h2::frame::{ExtensionFrame, Reason, DEFAULT_MAX_FRAME_SIZE};
 use crate::proto::h2::proto::{self, WindowSize};
 use crate::proto::h2::PollExt;
 
@@ -32,6 +33,10 @@ pub struct PingPong {
     inner: proto::UserPings,
 }
 
+pub struct Extensions {
+    inner: proto::UserExtensions,
+}
+
 pub struct Ping {
     _p: (),
 }
@@ -63,6 +68,10 @@ impl<B: Buf> SendStream<B> {
             .map_err_(Into::into)
     }
 
+    pub fn set_priority(&mut self, priority: Priority) {
+        self.inner.set_priority(priority)
+    }
+
     pub fn send_data(
         &mut self,
         data: B,
@@ -226,6 +235,45 @@ impl fmt::Debug for PingPong {
     }
 }
 
+impl Extensions {
+    pub(crate) fn new(inner: proto::UserExtensions) -> Self {
+        Extensions { inner }
+    }
+
+    pub fn send_frame(&mut self, frame: ExtensionFrame) -> Result<(), crate::proto::h2::Error> {
+        if !ExtensionFrame::is_extension_kind(frame.kind()) {
+            return Err(UserError::UnexpectedFrameType.into());
+        }
+        if frame.payload().len() > DEFAULT_MAX_FRAME_SIZE as usize {
+            return Err(UserError::PayloadTooBig.into());
+        }
+
+        self.inner.send_frame(frame).map_err(Into::into)
+    }
+
+    pub async fn frame(&mut self) -> Option<ExtensionFrame> {
+        futures_util::future::poll_fn(|cx| self.poll_frame(cx)).await
+    }
+
+    pub fn poll_frame(&mut self, cx: &mut Context) -> Poll<Option<ExtensionFrame>> {
+        self.inner.poll_frame(cx)
+    }
+
+    pub fn remote_setting(&self, id: u16) -> Option<u32> {
+        self.inner.remote_setting(id)
+    }
+
+    pub fn remote_settings(&self) -> Vec<(u16, u32)> {
+        self.inner.remote_settings()
+    }
+}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Extensions").finish()
+    }
+}
+
 impl Ping {
     pub fn opaque() -> Ping {
         Ping { _p: () }
@@ -0,0 +1,354 @@
+use cynthia::future::future::{or, pending};
+use cynthia::future::{timeout, Future};
+use cynthia::io::Timer;
+use cynthia::net::transport::AsyncToSocketAddrs;
+use cynthia::platform::dup;
+use cynthia::platform::event::Event;
+use cynthia::runtime::Async;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{self, ErrorKind};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::common::http_types::{Request, Response};
+use crate::common::tls::{read_client_hello, server_name, TlsAcceptor, TlsStream};
+use crate::proto::h1::rewind::Rewind;
+use crate::proto::h1::ServerOptions;
+use crate::proto::{serve_tls_with_opts, serve_with_opts};
+
+const CLIENT_HELLO_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub async fn listen<F, Fut>(
+    listener: Async<TcpListener>,
+    endpoint: F,
+    opts: ServerOptions,
+) -> crate::common::http_types::Result<()>
+where
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+{
+    Server::new(listener).with_opts(opts).serve(endpoint).await
+}
+
+pub struct Server {
+    listener: Async<TcpListener>,
+    tls: Option<TlsAcceptor>,
+    opts: ServerOptions,
+    max_connections: Option<usize>,
+    max_connections_per_ip: Option<usize>,
+    accept_backoff: Duration,
+    max_accept_backoff: Duration,
+}
+
+impl Server {
+    pub fn new(listener: Async<TcpListener>) -> Self {
+        Self {
+            listener,
+            tls: None,
+            opts: ServerOptions::default(),
+            max_connections: None,
+            max_connections_per_ip: None,
+            accept_backoff: Duration::from_millis(5),
+            max_accept_backoff: Duration::from_secs(1),
+        }
+    }
+
+    pub async fn bind<A: AsyncToSocketAddrs>(addr: A) -> io::Result<Self> {
+        Ok(Self::new(Async::<TcpListener>::bind(addr).await?))
+    }
+
+    pub fn tls(mut self, acceptor: TlsAcceptor) -> Self {
+        self.tls = Some(acceptor);
+        self
+    }
+
+    pub fn with_opts(mut self, opts: ServerOptions) -> Self {
+        self.opts = opts;
+        self
+    }
+
+    pub fn max_connections(mut self, max: Option<usize>) -> Self {
+        self.max_connections = max;
+        self
+    }
+
+    pub fn max_connections_per_ip(mut self, max: Option<usize>) -> Self {
+        self.max_connections_per_ip = max;
+        self
+    }
+
+    pub fn accept_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.accept_backoff = initial;
+        self.max_accept_backoff = max;
+        self
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.get_ref().local_addr()
+    }
+
+    pub async fn serve<F, Fut>(self, endpoint: F) -> crate::common::http_types::Result<()>
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+    {
+        let endpoint = Arc::new(endpoint);
+        let connections = Connections::default();
+        let mut backoff = None;
+
+        loop {
+            if let Some(max) = self.max_connections {
+                let available = or(
+                    async {
+                        connections.wait_below(max).await;
+                        true
+                    },
+                    async {
+                        self.opts.shutdown_signaled().await;
+                        false
+                    },
+                );
+                if !available.await {
+                    break;
+                }
+            }
+
+            let accept = self.listener.accept();
+            let shutdown = self.opts.shutdown_signaled();
+            let accepted = or(async { Some(accept.await) }, async {
+                shutdown.await;
+                None
+            })
+            .await;
+
+            let (stream, peer_addr) = match accepted {
+                Some(Ok(accepted)) => {
+                    backoff = None;
+                    accepted
+                }
+                Some(Err(e)) if is_connection_error(&e) => continue,
+                Some(Err(e)) => {
+                    let delay = match backoff {
+                        Some(delay) => self.max_accept_backoff.min(delay * 2),
+                        None => self.accept_backoff,
+                    };
+                    backoff = Some(delay);
+                    tracing::debug!("accept error: {}; retrying in {:?}", e, delay);
+
+                    let slept = or(
+                        async {
+                            Timer::after(delay).await;
+                            true
+                        },
+                        async {
+                            self.opts.shutdown_signaled().await;
+                            false
+                        },
+                    );
+                    if !slept.await {
+                        break;
+                    }
+                    continue;
+                }
+                None => break,
+            };
+
+            let guard = match connections.track(peer_addr.ip(), self.max_connections_per_ip) {
+                Some(guard) => guard,
+                None => {
+                    tracing::debug!("refusing connection from {}", peer_addr);
+                    continue;
+                }
+            };
+
+            let tls = self.tls.clone();
+            let endpoint = endpoint.clone();
+            let opts = self.opts.clone();
+            cynthia::runtime::spawn(async move {
+                let shutdown = opts.shutdown.clone();
+                let endpoint = move |req| endpoint(req);
+                let served = serve_connection(stream, peer_addr, tls, endpoint, opts);
+                let aborted = async {
+                    match shutdown {
+                        Some(shutdown) => shutdown.aborted().await,
+                        None => pending().await,
+                    }
+                    Ok(())
+                };
+                if let Err(e) = or(served, aborted).await {
+                    tracing::debug!("connection error: {}", e);
+                }
+                drop(guard);
+            })
+            .detach();
+        }
+
+        drop(self.listener);
+
+        let drained = match self.opts.shutdown_timeout {
+            Some(duration) => timeout(duration, connections.wait_below(1)).await.is_ok(),
+            None => {
+                connections.wait_below(1).await;
+                true
+            }
+        };
+
+        if !drained {
+            tracing::debug!("aborting {} connections", connections.active());
+            if let Some(shutdown) = &self.opts.shutdown {
+                shutdown.abrupt_shutdown();
+            }
+            connections.wait_below(1).await;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Server {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Server")
+            .field("local_addr", &self.local_addr().ok())
+            .field("tls", &self.tls)
+            .field("opts", &self.opts)
+            .field("max_connections", &self.max_connections)
+            .field("max_connections_per_ip", &self.max_connections_per_ip)
+            .field("accept_backoff", &self.accept_backoff)
+            .field("max_accept_backoff", &self.max_accept_backoff)
+            .finish()
+    }
+}
+
+async fn serve_connection<F, Fut>(
+    stream: Async<TcpStream>,
+    peer_addr: SocketAddr,
+    tls: Option<TlsAcceptor>,
+    endpoint: F,
+    opts: ServerOptions,
+) -> crate::common::http_types::Result<()>
+where
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+{
+    let mut info = opts.connection_info.clone().unwrap_or_default();
+    info = info.with_peer_addr(peer_addr);
+    if let Ok(local_addr) = stream.get_ref().local_addr() {
+        info = info.with_local_addr(local_addr);
+    }
+
+    let tls = match tls {
+        Some(tls) => tls,
+        None => {
+            let opts = opts.connection_info(info);
+            return serve_with_opts(dup::Arc::new(stream), endpoint, opts).await;
+        }
+    };
+
+    let fut = accept_tls(stream, tls);
+    let (server_name, stream) = if let Some(timeout_duration) = opts.headers_timeout {
+        match timeout(timeout_duration, fut).await {
+            Ok(accepted) => accepted?,
+            Err(_) => return Ok(()), /* timeout */
+        }
+    } else {
+        fut.await?
+    };
+
+    if let Some(name) = server_name {
+        let tls_info = info.take_tls().unwrap_or_default().with_server_name(name);
+        info = info.with_tls(tls_info);
+    }
+    let opts = opts.connection_info(info);
+    serve_tls_with_opts(stream, endpoint, opts).await
+}
+
+async fn accept_tls(
+    mut stream: Async<TcpStream>,
+    tls: TlsAcceptor,
+) -> crate::common::http_types::Result<(Option<String>, TlsStream<Rewind<Async<TcpStream>>>)> {
+    let hello = match timeout(CLIENT_HELLO_TIMEOUT, read_client_hello(&mut stream)).await {
+        Ok(hello) => hello?,
+        Err(_) => return Err(io::Error::from(ErrorKind::TimedOut).into()),
+    };
+    let server_name = server_name(&hello);
+    let stream = tls.accept(Rewind::new_buffered(stream, hello)).await?;
+    Ok((server_name, stream))
+}
+
+fn is_connection_error(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        ErrorKind::ConnectionRefused | ErrorKind::ConnectionAborted | ErrorKind::ConnectionReset
+    )
+}
+
+#[derive(Clone, Debug, Default)]
+struct Connections {
+    inner: Arc<ConnectionsInner>,
+}
+
+#[derive(Debug, Default)]
+struct ConnectionsInner {
+    active: AtomicUsize,
+    per_ip: Mutex<HashMap<IpAddr, usize>>,
+    event: Event,
+}
+
+impl Connections {
+    fn track(&self, ip: IpAddr, max_per_ip: Option<usize>) -> Option<ConnectionGuard> {
+        let mut per_ip = self.inner.per_ip.lock().unwrap();
+        let count = per_ip.get(&ip).copied().unwrap_or(0);
+        if max_per_ip.map_or(false, |max| count >= max) {
+            return None;
+        }
+        per_ip.insert(ip, count + 1);
+        self.inner.active.fetch_add(1, Ordering::SeqCst);
+
+        Some(ConnectionGuard {
+            inner: self.inner.clone(),
+            ip,
+        })
+    }
+
+    fn active(&self) -> usize {
+        self.inner.active.load(Ordering::SeqCst)
+    }
+
+    async fn wait_below(&self, max: usize) {
+        loop {
+            if self.active() < max {
+                return;
+            }
+            let listener = self.inner.event.listen();
+            if self.active() < max {
+                return;
+            }
+            listener.await;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ConnectionGuard {
+    inner: Arc<ConnectionsInner>,
+    ip: IpAddr,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        let mut per_ip = self.inner.per_ip.lock().unwrap();
+        if let Some(count) = per_ip.get_mut(&self.ip) {
+            *count -= 1;
+            if *count == 0 {
+                per_ip.remove(&self.ip);
+            }
+        }
+        drop(per_ip);
+
+        self.inner.active.fetch_sub(1, Ordering::SeqCst);
+        self.inner.event.notify(usize::MAX);
+    }
+}
@@ -1,2 +1,11 @@
+mod connection_info;
 pub mod h1;
 pub mod h2;
+mod listener;
+mod serve;
+mod shutdown;
+
+pub use connection_info::{ConnectionInfo, TlsInfo};
+pub use listener::{listen, Server};
+pub use serve::{serve, serve_tls, serve_tls_with_opts, serve_with_opts, ALPN_H2, ALPN_HTTP11};
+pub use shutdown::Shutdown;
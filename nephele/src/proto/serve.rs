@@ -0,0 +1,125 @@
+use cynthia::future::prelude::*;
+use cynthia::future::swap::{AsyncRead, AsyncWrite};
+use cynthia::future::{timeout, Future, TimeoutError};
+use cynthia::platform::dup;
+use std::sync::Arc;
+
+use crate::common::http_types::{Request, Response};
+use crate::common::tls::TlsStream;
+use crate::proto::h1;
+use crate::proto::h1::rewind::Rewind;
+use crate::proto::h1::server::Server;
+use crate::proto::h1::ServerOptions;
+use crate::proto::h2::server::PREFACE;
+use crate::proto::h2::{accept_h2c, accept_with_opts};
+
+pub const ALPN_H2: &str = "h2";
+pub const ALPN_HTTP11: &str = "http/1.1";
+
+pub async fn serve<RW, F, Fut>(io: RW, endpoint: F) -> crate::common::http_types::Result<()>
+where
+    RW: AsyncRead + AsyncWrite + Clone + Send + Sync + Unpin + 'static,
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+{
+    serve_with_opts(io, endpoint, ServerOptions::default()).await
+}
+
+pub async fn serve_with_opts<RW, F, Fut>(
+    mut io: RW,
+    endpoint: F,
+    opts: ServerOptions,
+) -> crate::common::http_types::Result<()>
+where
+    RW: AsyncRead + AsyncWrite + Clone + Send + Sync + Unpin + 'static,
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+{
+    let fut = read_preface(&mut io);
+    let peeked = if let Some(timeout_duration) = opts.headers_timeout {
+        match timeout(timeout_duration, fut).await {
+            Ok(peeked) => peeked?,
+            Err(TimeoutError { .. }) => return Ok(()), /* timeout */
+        }
+    } else {
+        fut.await?
+    };
+
+    if peeked[..] == PREFACE[..] {
+        let io = Rewind::new_buffered(io, peeked);
+        return accept_with_opts(io, endpoint, opts).await;
+    }
+
+    let h2_opts = opts.clone();
+    let endpoint = Arc::new(endpoint);
+    let h1_endpoint = endpoint.clone();
+    let mut server = Server::new(io.clone(), move |req| h1_endpoint(req))
+        .with_opts(opts)
+        .with_buffered(peeked)
+        .with_h2c();
+    server.accept().await?;
+
+    match server.take_h2c() {
+        Some((req, settings, buffered)) => {
+            let io = Rewind::new_buffered(io, buffered);
+            accept_h2c(io, endpoint, &settings, req, h2_opts).await
+        }
+        None => Ok(()),
+    }
+}
+
+pub async fn serve_tls<S, F, Fut>(
+    stream: TlsStream<S>,
+    endpoint: F,
+) -> crate::common::http_types::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Send + Sync + Unpin + 'static,
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+{
+    serve_tls_with_opts(stream, endpoint, ServerOptions::default()).await
+}
+
+pub async fn serve_tls_with_opts<S, F, Fut>(
+    stream: TlsStream<S>,
+    endpoint: F,
+    mut opts: ServerOptions,
+) -> crate::common::http_types::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Send + Sync + Unpin + 'static,
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+{
+    let mut info = opts.connection_info.take().unwrap_or_default();
+    let tls = info.take_tls().unwrap_or_default().with_stream(&stream)?;
+    let opts = opts.connection_info(info.with_tls(tls));
+
+    match stream.negotiated_alpn()? {
+        Some(protocol) if protocol == ALPN_H2.as_bytes() => {
+            accept_with_opts(stream, endpoint, opts).await
+        }
+        _ => {
+            let io = dup::Arc::new(dup::Mutex::new(stream));
+            h1::accept_with_opts(io, endpoint, opts).await
+        }
+    }
+}
+
+async fn read_preface<RW>(io: &mut RW) -> crate::common::http_types::Result<Vec<u8>>
+where
+    RW: AsyncRead + Unpin,
+{
+    let mut buf = vec![0; PREFACE.len()];
+    let mut len = 0;
+
+    while len < PREFACE.len() && buf[..len] == PREFACE[..len] {
+        let bytes_read = io.read(&mut buf[len..]).await?;
+        if bytes_read == 0 {
+            break; /* EOF */
+        }
+        len += bytes_read;
+    }
+
+    buf.truncate(len);
+    Ok(buf)
+}
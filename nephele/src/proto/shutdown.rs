@@ -0,0 +1,67 @@
+use cynthia::platform::event::Event;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Clone, Default)]
+pub struct Shutdown {
+    inner: Arc<ShutdownInner>,
+}
+
+#[derive(Default)]
+struct ShutdownInner {
+    graceful: AtomicBool,
+    abrupt: AtomicBool,
+    event: Event,
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn graceful_shutdown(&self) {
+        self.inner.graceful.store(true, Ordering::SeqCst);
+        self.inner.event.notify(usize::MAX);
+    }
+
+    pub fn abrupt_shutdown(&self) {
+        self.inner.graceful.store(true, Ordering::SeqCst);
+        self.inner.abrupt.store(true, Ordering::SeqCst);
+        self.inner.event.notify(usize::MAX);
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        self.inner.graceful.load(Ordering::SeqCst)
+    }
+
+    pub async fn signaled(&self) {
+        self.wait_for(&self.inner.graceful).await
+    }
+
+    pub(crate) async fn aborted(&self) {
+        self.wait_for(&self.inner.abrupt).await
+    }
+
+    async fn wait_for(&self, flag: &AtomicBool) {
+        loop {
+            if flag.load(Ordering::SeqCst) {
+                return;
+            }
+            let listener = self.inner.event.listen();
+            if flag.load(Ordering::SeqCst) {
+                return;
+            }
+            listener.await;
+        }
+    }
+}
+
+impl fmt::Debug for Shutdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shutdown")
+            .field("graceful", &self.inner.graceful.load(Ordering::SeqCst))
+            .field("abrupt", &self.inner.abrupt.load(Ordering::SeqCst))
+            .finish()
+    }
+}
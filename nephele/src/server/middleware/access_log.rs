@@ -0,0 +1,48 @@
+use std::time::Instant;
+
+use crate::common::http_types::Request;
+use crate::server::middleware::{Middleware, Next};
+use crate::server::BoxFuture;
+
+#[derive(Debug, Default, Clone)]
+pub struct AccessLog;
+
+impl AccessLog {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Middleware for AccessLog {
+    fn handle<'a>(&'a self, req: Request, next: Next<'a>) -> BoxFuture<'a> {
+        Box::pin(async move {
+            let method = req.method();
+            let path = req.url().path().to_string();
+            let remote = req.remote().map(String::from);
+            let start = Instant::now();
+
+            let res = next.run(req).await;
+            let elapsed = start.elapsed();
+            match &res {
+                Ok(res) => tracing::info!(
+                    method = %method,
+                    path = %path,
+                    remote = ?remote,
+                    status = %res.status(),
+                    elapsed = ?elapsed,
+                    "request handled"
+                ),
+                Err(e) => tracing::info!(
+                    method = %method,
+                    path = %path,
+                    remote = ?remote,
+                    status = %e.status(),
+                    elapsed = ?elapsed,
+                    error = %e,
+                    "request failed"
+                ),
+            }
+            res
+        })
+    }
+}
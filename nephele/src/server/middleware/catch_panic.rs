@@ -0,0 +1,50 @@
+use cynthia::runtime::task::Poll;
+use futures_util::future::poll_fn;
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::common::http_types::{Request, Response, StatusCode};
+use crate::server::middleware::{Middleware, Next};
+use crate::server::BoxFuture;
+
+#[derive(Debug, Default, Clone)]
+pub struct CatchPanic;
+
+impl CatchPanic {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Middleware for CatchPanic {
+    fn handle<'a>(&'a self, req: Request, next: Next<'a>) -> BoxFuture<'a> {
+        let mut fut = match catch_unwind(AssertUnwindSafe(|| next.run(req))) {
+            Ok(fut) => fut,
+            Err(panic) => return Box::pin(async move { Ok(internal_error(panic)) }),
+        };
+
+        Box::pin(async move {
+            let polled = poll_fn(|cx| {
+                let poll = catch_unwind(AssertUnwindSafe(|| fut.as_mut().poll(cx)));
+                match poll {
+                    Ok(poll) => poll.map(Ok),
+                    Err(panic) => Poll::Ready(Err(panic)),
+                }
+            });
+            match polled.await {
+                Ok(res) => res,
+                Err(panic) => Ok(internal_error(panic)),
+            }
+        })
+    }
+}
+
+fn internal_error(panic: Box<dyn Any + Send>) -> Response {
+    let message = panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+    tracing::error!("endpoint panicked: {}", message);
+    Response::new(StatusCode::InternalServerError)
+}
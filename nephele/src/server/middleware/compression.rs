@@ -0,0 +1,165 @@
+use crate::common::http_types::cache::{CacheControl, CacheDirective};
+use crate::common::http_types::conditional::{ETag, Vary};
+use crate::common::http_types::content::{AcceptEncoding, ContentEncoding, Encoding};
+use crate::common::http_types::headers::{
+    ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH, ETAG, VARY,
+};
+use crate::common::http_types::{Request, Response, StatusCode};
+use crate::server::middleware::{Middleware, Next};
+use crate::server::BoxFuture;
+
+const MIN_SIZE: usize = 1024;
+
+const MIME_TYPES: [&str; 8] = [
+    "text/",
+    "application/json",
+    "application/javascript",
+    "application/xml",
+    "application/wasm",
+    "image/svg+xml",
+    "application/manifest+json",
+    "application/x-ndjson",
+];
+
+#[derive(Debug, Clone)]
+pub struct Compression {
+    encodings: Vec<Encoding>,
+    min_size: usize,
+    mime_types: Vec<String>,
+}
+
+impl Compression {
+    pub fn new() -> Self {
+        Self {
+            encodings: vec![Encoding::Brotli, Encoding::Gzip, Encoding::Deflate],
+            min_size: MIN_SIZE,
+            mime_types: MIME_TYPES.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    pub fn encodings(mut self, encodings: Vec<Encoding>) -> Self {
+        self.encodings = encodings;
+        self
+    }
+
+    pub fn min_size(mut self, size: usize) -> Self {
+        self.min_size = size;
+        self
+    }
+
+    pub fn mime_types<I, S>(mut self, mime_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.mime_types = mime_types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    fn is_compressible(&self, res: &Response) -> bool {
+        let status = res.status();
+        if status.is_informational()
+            || status == StatusCode::NoContent
+            || status == StatusCode::NotModified
+            || status == StatusCode::PartialContent
+        {
+            return false;
+        }
+        if res.header(CONTENT_ENCODING).is_some() {
+            return false;
+        }
+        if res.len().map_or(false, |len| len < self.min_size) {
+            return false;
+        }
+        let no_transform = CacheControl::from_headers(res)
+            .ok()
+            .flatten()
+            .map_or(false, |cache| cache.contains(&CacheDirective::NoTransform));
+        if no_transform {
+            return false;
+        }
+
+        match res.content_type() {
+            Some(mime) => self
+                .mime_types
+                .iter()
+                .any(|allowed| mime.essence().starts_with(allowed.as_str())),
+            None => false,
+        }
+    }
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Middleware for Compression {
+    fn handle<'a>(&'a self, req: Request, next: Next<'a>) -> BoxFuture<'a> {
+        let accept = AcceptEncoding::from_headers(&req).ok().flatten();
+
+        Box::pin(async move {
+            let mut res = next.run(req).await?;
+            if !self.is_compressible(&res) {
+                return Ok(res);
+            }
+            append_vary(&mut res);
+
+            let mut available = self.encodings.clone();
+            available.push(Encoding::Identity);
+            let encoding = match accept.and_then(|accept| accept.negotiate(&available)) {
+                Some(encoding) if encoding != Encoding::Identity => encoding,
+                _ => return Ok(res),
+            };
+
+            let body = res.take_body().compress(encoding);
+            res.set_body(body);
+            res.remove_header(CONTENT_LENGTH);
+            ContentEncoding::new(encoding).apply(&mut res);
+            match ETag::from_headers(&res) {
+                Ok(Some(etag)) => etag.into_weak().apply(&mut res),
+                Ok(None) => {}
+                Err(_) => weaken_raw_etag(&mut res),
+            }
+            Ok(res)
+        })
+    }
+}
+
+fn append_vary(res: &mut Response) {
+    let mut vary = match Vary::from_headers(&*res) {
+        Ok(vary) => vary.unwrap_or_default(),
+        Err(_) => return append_raw_vary(res),
+    };
+    if !vary.contains(ACCEPT_ENCODING) {
+        vary.push(ACCEPT_ENCODING);
+        vary.apply(res);
+    }
+}
+
+fn append_raw_vary(res: &mut Response) {
+    let varies = res.header(VARY).map_or(false, |values| {
+        values
+            .iter()
+            .flat_map(|value| value.as_str().split(','))
+            .any(|name| {
+                let name = name.trim();
+                name == "*" || name.eq_ignore_ascii_case(ACCEPT_ENCODING.as_str())
+            })
+    });
+    if !varies {
+        res.append_header(VARY, ACCEPT_ENCODING.as_str());
+    }
+}
+
+fn weaken_raw_etag(res: &mut Response) {
+    if let Some(etag) = res
+        .header(ETAG)
+        .map(|etag| etag.last().as_str().to_string())
+    {
+        if !etag.starts_with("W/") {
+            res.insert_header(ETAG, format!("W/{}", etag));
+        }
+    }
+}
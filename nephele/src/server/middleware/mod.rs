@@ -0,0 +1,103 @@
+use cynthia::future::Future;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::common::http_types::{Request, Response};
+use crate::server::BoxFuture;
+
+mod access_log;
+mod catch_panic;
+mod compression;
+mod request_id;
+
+pub use access_log::AccessLog;
+pub use catch_panic::CatchPanic;
+pub use compression::Compression;
+pub use request_id::{RequestId, SetRequestId};
+
+type Endpoint = dyn Fn(Request) -> BoxFuture<'static> + Send + Sync + 'static;
+
+pub trait Middleware: Send + Sync + 'static {
+    fn handle<'a>(&'a self, req: Request, next: Next<'a>) -> BoxFuture<'a>;
+}
+
+impl<F> Middleware for F
+where
+    F: for<'a> Fn(Request, Next<'a>) -> BoxFuture<'a> + Send + Sync + 'static,
+{
+    fn handle<'a>(&'a self, req: Request, next: Next<'a>) -> BoxFuture<'a> {
+        self(req, next)
+    }
+}
+
+pub struct Next<'a> {
+    endpoint: &'a Endpoint,
+    middleware: &'a [Box<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub fn run(mut self, req: Request) -> BoxFuture<'a> {
+        match self.middleware.split_first() {
+            Some((current, rest)) => {
+                self.middleware = rest;
+                current.handle(req, self)
+            }
+            None => (self.endpoint)(req),
+        }
+    }
+}
+
+impl fmt::Debug for Next<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Next")
+            .field("remaining", &self.middleware.len())
+            .finish()
+    }
+}
+
+#[derive(Default)]
+pub struct Builder {
+    middleware: Vec<Box<dyn Middleware>>,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<M: Middleware>(mut self, middleware: M) -> Self {
+        self.middleware.push(Box::new(middleware));
+        self
+    }
+
+    pub fn endpoint<F, Fut>(
+        self,
+        endpoint: F,
+    ) -> impl Fn(Request) -> BoxFuture<'static> + Clone + Send + Sync + 'static
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+    {
+        let endpoint: Box<Endpoint> = Box::new(move |req| Box::pin(endpoint(req)));
+        let chain = Arc::new((self.middleware, endpoint));
+
+        move |req| {
+            let chain = chain.clone();
+            Box::pin(async move {
+                let next = Next {
+                    endpoint: &*chain.1,
+                    middleware: &chain.0,
+                };
+                next.run(req).await
+            })
+        }
+    }
+}
+
+impl fmt::Debug for Builder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("middleware", &self.middleware.len())
+            .finish()
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+
+use crate::common::http_types::headers::{HeaderName, X_REQUEST_ID};
+use crate::common::http_types::Request;
+use crate::server::middleware::{Middleware, Next};
+use crate::server::BoxFuture;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestId(String);
+
+impl RequestId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    pub fn generate() -> Self {
+        Self(format!("{:032x}", cynthia::utils::rander::u128(..)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SetRequestId {
+    header: HeaderName,
+    trust_incoming: bool,
+}
+
+impl SetRequestId {
+    pub fn new() -> Self {
+        Self {
+            header: X_REQUEST_ID,
+            trust_incoming: true,
+        }
+    }
+
+    pub fn header(mut self, name: HeaderName) -> Self {
+        self.header = name;
+        self
+    }
+
+    pub fn trust_incoming(mut self, trust: bool) -> Self {
+        self.trust_incoming = trust;
+        self
+    }
+}
+
+impl Default for SetRequestId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Middleware for SetRequestId {
+    fn handle<'a>(&'a self, mut req: Request, next: Next<'a>) -> BoxFuture<'a> {
+        let incoming = req
+            .header(&self.header)
+            .map(|values| values.last().as_str())
+            .filter(|id| self.trust_incoming && !id.is_empty() && id.len() <= 128)
+            .map(RequestId::new);
+        let id = incoming.unwrap_or_else(RequestId::generate);
+        req.ext_mut().insert(id.clone());
+
+        Box::pin(async move {
+            let mut res = next.run(req).await?;
+            if res.header(&self.header).is_none() {
+                res.insert_header(&self.header, id.as_str());
+            }
+            Ok(res)
+        })
+    }
+}
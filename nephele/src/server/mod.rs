@@ -0,0 +1,14 @@
+use cynthia::future::Future;
+use std::pin::Pin;
+
+use crate::common::http_types::Response;
+
+pub mod middleware;
+mod router;
+mod serve_dir;
+
+pub use router::{Params, Router};
+pub use serve_dir::ServeDir;
+
+pub type BoxFuture<'a> =
+    Pin<Box<dyn Future<Output = crate::common::http_types::Result<Response>> + Send + 'a>>;
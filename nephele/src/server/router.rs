@@ -0,0 +1,315 @@
+use cynthia::future::Future;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::common::http_types::other::Allow;
+use crate::common::http_types::{Method, Request, Response, StatusCode};
+use crate::server::BoxFuture;
+
+type Handler = Arc<dyn Fn(Request) -> BoxFuture<'static> + Send + Sync + 'static>;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    params: Vec<(String, String)>,
+}
+
+impl Params {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.params.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.params.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Param(String),
+    Wildcard(Option<String>),
+}
+
+impl Segment {
+    fn rank(&self) -> u8 {
+        match self {
+            Segment::Literal(_) => 0,
+            Segment::Param(_) => 1,
+            Segment::Wildcard(_) => 2,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Pattern {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+impl Pattern {
+    fn parse(source: &str) -> Self {
+        assert!(
+            source.starts_with('/'),
+            "route pattern {:?} must start with '/'",
+            source
+        );
+
+        let mut segments = Vec::new();
+        let mut names = Vec::new();
+        let parts = source[1..].split('/').collect::<Vec<_>>();
+        for (i, part) in parts.iter().enumerate() {
+            let segment = if let Some(name) = part.strip_prefix(':') {
+                assert!(!name.is_empty(), "empty parameter name in {:?}", source);
+                names.push(name);
+                Segment::Param(name.to_string())
+            } else if let Some(name) = part.strip_prefix('*') {
+                assert!(
+                    i == parts.len() - 1,
+                    "wildcard must be the last segment of {:?}",
+                    source
+                );
+                if name.is_empty() {
+                    Segment::Wildcard(None)
+                } else {
+                    names.push(name);
+                    Segment::Wildcard(Some(name.to_string()))
+                }
+            } else {
+                Segment::Literal(part.to_string())
+            };
+            segments.push(segment);
+        }
+
+        let mut sorted = names.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert!(
+            sorted.len() == names.len(),
+            "duplicate parameter name in {:?}",
+            source
+        );
+
+        Self {
+            source: source.to_string(),
+            segments,
+        }
+    }
+
+    fn matches(&self, path: &str) -> Option<Params> {
+        let path = path.strip_prefix('/').unwrap_or(path);
+        let mut rest = Some(path);
+        let mut params = Vec::new();
+
+        for segment in &self.segments {
+            let remaining = rest?;
+            match segment {
+                Segment::Wildcard(name) => {
+                    if let Some(name) = name {
+                        params.push((name.clone(), percent_decode(remaining)));
+                    }
+                    return Some(Params { params });
+                }
+                _ => {
+                    let (part, next) = match remaining.find('/') {
+                        Some(i) => (&remaining[..i], Some(&remaining[i + 1..])),
+                        None => (remaining, None),
+                    };
+                    match segment {
+                        Segment::Literal(literal) if literal == part => {}
+                        Segment::Param(name) if !part.is_empty() => {
+                            params.push((name.clone(), percent_decode(part)));
+                        }
+                        _ => return None,
+                    }
+                    rest = next;
+                }
+            }
+        }
+
+        match rest {
+            None => Some(Params { params }),
+            Some(_) => None,
+        }
+    }
+
+    fn ranks(&self) -> Vec<u8> {
+        self.segments.iter().map(Segment::rank).collect()
+    }
+}
+
+struct Route {
+    method: Method,
+    pattern: Pattern,
+    handler: Handler,
+}
+
+impl fmt::Debug for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Route")
+            .field("method", &self.method)
+            .field("pattern", &self.pattern.source)
+            .finish()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Router {
+    routes: Vec<Route>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn route<F, Fut>(mut self, method: Method, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+    {
+        self.routes.push(Route {
+            method,
+            pattern: Pattern::parse(pattern),
+            handler: Arc::new(move |req| Box::pin(handler(req))),
+        });
+        self
+    }
+
+    pub fn get<F, Fut>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+    {
+        self.route(Method::Get, pattern, handler)
+    }
+
+    pub fn post<F, Fut>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+    {
+        self.route(Method::Post, pattern, handler)
+    }
+
+    pub fn put<F, Fut>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+    {
+        self.route(Method::Put, pattern, handler)
+    }
+
+    pub fn patch<F, Fut>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+    {
+        self.route(Method::Patch, pattern, handler)
+    }
+
+    pub fn delete<F, Fut>(self, pattern: &str, handler: F) -> Self
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = crate::common::http_types::Result<Response>> + Send + 'static,
+    {
+        self.route(Method::Delete, pattern, handler)
+    }
+
+    pub fn handle(&self, mut req: Request) -> BoxFuture<'static> {
+        let method = req.method();
+        let mut best: Option<(Vec<u8>, &Route, Params)> = None;
+        let mut fallback: Option<(Vec<u8>, &Route, Params)> = None;
+        let mut allowed = Vec::new();
+
+        for route in &self.routes {
+            let params = match route.pattern.matches(req.url().path()) {
+                Some(params) => params,
+                None => continue,
+            };
+            allowed.push(route.method);
+            if route.method == Method::Get {
+                allowed.push(Method::Head);
+            }
+
+            let slot = if route.method == method {
+                &mut best
+            } else if route.method == Method::Get && method == Method::Head {
+                &mut fallback
+            } else {
+                continue;
+            };
+            let ranks = route.pattern.ranks();
+            if slot.as_ref().map_or(true, |(best, _, _)| ranks < *best) {
+                *slot = Some((ranks, route, params));
+            }
+        }
+
+        if let Some((_, route, params)) = best.or(fallback) {
+            req.ext_mut().insert(params);
+            return (route.handler)(req);
+        }
+
+        let res = if allowed.is_empty() {
+            Response::new(StatusCode::NotFound)
+        } else {
+            allowed.sort_unstable_by_key(Method::to_string);
+            let mut allow = Allow::new();
+            for method in allowed {
+                allow.push(method);
+            }
+
+            let mut res = Response::new(StatusCode::MethodNotAllowed);
+            allow.apply(&mut res);
+            res
+        };
+        Box::pin(async move { Ok(res) })
+    }
+
+    pub fn into_endpoint(
+        self,
+    ) -> impl Fn(Request) -> BoxFuture<'static> + Clone + Send + Sync + 'static {
+        let router = Arc::new(self);
+        move |req| router.handle(req)
+    }
+}
+
+pub(crate) fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    match String::from_utf8(decoded) {
+        Ok(decoded) => decoded,
+        Err(_) => input.to_string(),
+    }
+}
@@ -0,0 +1,357 @@
+use cynthia::fs::File;
+use cynthia::future::{prelude::*, swap};
+use std::io::{ErrorKind, SeekFrom};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use crate::common::http_types::body::{guess_ext, peek_mime};
+use crate::common::http_types::conditional::{ETag, IfModifiedSince, IfNoneMatch, LastModified};
+use crate::common::http_types::headers::{ACCEPT_RANGES, CONTENT_RANGE, IF_RANGE, RANGE};
+use crate::common::http_types::other::Allow;
+use crate::common::http_types::{mime, Body, Method, Mime, Request, Response, StatusCode};
+use crate::proto::h1::date::parse_http_date;
+use crate::server::router::percent_decode;
+use crate::server::BoxFuture;
+
+const MAX_RANGES: usize = 32;
+
+type Reader = Box<dyn AsyncBufRead + Unpin + Send + Sync + 'static>;
+
+#[derive(Debug, Clone)]
+pub struct ServeDir {
+    root: PathBuf,
+    prefix: String,
+    index: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Ranges {
+    Full,
+    Partial(Vec<(u64, u64)>),
+    Unsatisfiable,
+}
+
+struct Entity {
+    path: PathBuf,
+    len: u64,
+    etag: ETag,
+    modified: Option<LastModified>,
+}
+
+impl ServeDir {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            prefix: String::new(),
+            index: Some("index.html".to_string()),
+        }
+    }
+
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn index(mut self, index: Option<String>) -> Self {
+        self.index = index;
+        self
+    }
+
+    pub fn handle(&self, req: Request) -> BoxFuture<'static> {
+        let dir = self.clone();
+        Box::pin(async move { dir.serve(req).await })
+    }
+
+    pub fn into_endpoint(
+        self,
+    ) -> impl Fn(Request) -> BoxFuture<'static> + Clone + Send + Sync + 'static {
+        let dir = Arc::new(self);
+        move |req| {
+            let dir = dir.clone();
+            Box::pin(async move { dir.serve(req).await })
+        }
+    }
+
+    async fn serve(&self, req: Request) -> crate::common::http_types::Result<Response> {
+        let method = req.method();
+        if method != Method::Get && method != Method::Head {
+            let mut allow = Allow::new();
+            allow.push(Method::Get);
+            allow.push(Method::Head);
+
+            let mut res = Response::new(StatusCode::MethodNotAllowed);
+            allow.apply(&mut res);
+            return Ok(res);
+        }
+
+        let path = match self.resolve(req.url().path()) {
+            Some(path) => path,
+            None => return Ok(Response::new(StatusCode::NotFound)),
+        };
+        let (mut file, entity) = match self.open(path).await {
+            Ok(Some(opened)) => opened,
+            Ok(None) => return Ok(Response::new(StatusCode::NotFound)),
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                return Ok(Response::new(StatusCode::NotFound))
+            }
+            Err(err) if err.kind() == ErrorKind::PermissionDenied => {
+                return Ok(Response::new(StatusCode::Forbidden))
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if entity.not_modified(&req) {
+            let mut res = Response::new(StatusCode::NotModified);
+            entity.apply_validators(&mut res);
+            return Ok(res);
+        }
+
+        let mime = peek_mime(&mut file)
+            .await?
+            .or_else(|| guess_ext(&entity.path))
+            .unwrap_or(mime::BYTE_STREAM);
+
+        let ranges = match req.header(RANGE) {
+            Some(range) if method == Method::Get && entity.if_range(&req) => {
+                parse_ranges(range.last().as_str(), entity.len)
+            }
+            _ => Ranges::Full,
+        };
+
+        let mut res = match ranges {
+            Ranges::Full => {
+                let mut body =
+                    Body::from_reader(swap::BufferReader::new(file), Some(entity.len as usize));
+                body.set_mime(mime);
+
+                let mut res = Response::new(StatusCode::Ok);
+                res.set_body(body);
+                res
+            }
+            Ranges::Unsatisfiable => {
+                let mut res = Response::new(StatusCode::RequestedRangeNotSatisfiable);
+                res.insert_header(CONTENT_RANGE, format!("bytes */{}", entity.len));
+                res
+            }
+            Ranges::Partial(ranges) if ranges.len() == 1 => {
+                let (start, end) = ranges[0];
+                file.seek(SeekFrom::Start(start)).await?;
+                let reader = swap::BufferReader::new(file.take(end - start + 1));
+                let mut body = Body::from_reader(reader, Some((end - start + 1) as usize));
+                body.set_mime(mime);
+
+                let mut res = Response::new(StatusCode::PartialContent);
+                res.insert_header(
+                    CONTENT_RANGE,
+                    format!("bytes {}-{}/{}", start, end, entity.len),
+                );
+                res.set_body(body);
+                res
+            }
+            Ranges::Partial(ranges) => {
+                let mut res = Response::new(StatusCode::PartialContent);
+                res.set_body(multipart(&entity.path, &ranges, entity.len, &mime).await?);
+                res
+            }
+        };
+
+        res.insert_header(ACCEPT_RANGES, "bytes");
+        entity.apply_validators(&mut res);
+        Ok(res)
+    }
+
+    fn resolve(&self, path: &str) -> Option<PathBuf> {
+        let path = path.strip_prefix(self.prefix.as_str())?;
+        if !path.is_empty() && !path.starts_with('/') {
+            return None;
+        }
+
+        let mut resolved = self.root.clone();
+        for segment in path.split('/') {
+            let segment = percent_decode(segment);
+            match segment.as_str() {
+                "" | "." => continue,
+                ".." => return None,
+                _ => {}
+            }
+            if segment.contains(&['/', '\\', '\0'][..]) {
+                return None;
+            }
+            let mut components = Path::new(&segment).components();
+            match (components.next(), components.next()) {
+                (Some(Component::Normal(_)), None) => resolved.push(&segment),
+                _ => return None,
+            }
+        }
+        Some(resolved)
+    }
+
+    async fn open(&self, mut path: PathBuf) -> std::io::Result<Option<(File, Entity)>> {
+        let mut file = File::open(&path).await?;
+        let mut metadata = file.metadata().await?;
+        if metadata.is_dir() {
+            let index = match &self.index {
+                Some(index) => index,
+                None => return Ok(None),
+            };
+            path.push(index);
+            file = File::open(&path).await?;
+            metadata = file.metadata().await?;
+            if !metadata.is_file() {
+                return Ok(None);
+            }
+        }
+
+        let len = metadata.len();
+        let modified = metadata.modified().ok();
+        let stamp = modified
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |stamp| stamp.as_nanos());
+
+        let entity = Entity {
+            path,
+            len,
+            etag: ETag::Strong(format!("{:x}-{:x}", stamp, len)),
+            modified: modified.map(LastModified::new),
+        };
+        Ok(Some((file, entity)))
+    }
+}
+
+impl Entity {
+    fn apply_validators(&self, res: &mut Response) {
+        self.etag.apply(&mut *res);
+        if let Some(last_modified) = self.modified {
+            last_modified.apply(res);
+        }
+    }
+
+    fn not_modified(&self, req: &Request) -> bool {
+        match IfNoneMatch::from_headers(req) {
+            Ok(Some(if_none_match)) => return if_none_match.matches(&self.etag),
+            Ok(None) => {}
+            Err(_) => return false,
+        }
+
+        match (IfModifiedSince::from_headers(req), self.modified) {
+            (Ok(Some(since)), Some(modified)) => !since.is_modified(modified.modified()),
+            _ => false,
+        }
+    }
+
+    fn if_range(&self, req: &Request) -> bool {
+        let value = match req.header(IF_RANGE) {
+            Some(value) => value.last().as_str().trim(),
+            None => return true,
+        };
+        if value.starts_with('"') || value.starts_with("W/") {
+            return value
+                .parse::<ETag>()
+                .map_or(false, |etag| etag.strong_eq(&self.etag));
+        }
+        match (parse_http_date(value), self.modified) {
+            (Ok(at), Some(modified)) => at == modified.modified(),
+            _ => false,
+        }
+    }
+}
+
+fn parse_ranges(value: &str, len: u64) -> Ranges {
+    let specs = match value.split_once('=') {
+        Some((unit, specs)) if unit.trim().eq_ignore_ascii_case("bytes") => specs,
+        _ => return Ranges::Full,
+    };
+
+    let mut ranges = Vec::new();
+    let mut parsed = 0;
+    for spec in specs.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let (start, end) = match spec.split_once('-') {
+            Some((start, end)) => (start.trim(), end.trim()),
+            None => return Ranges::Full,
+        };
+        parsed += 1;
+
+        let range = match (start.parse::<u64>(), end.parse::<u64>()) {
+            (Err(_), Ok(suffix)) if start.is_empty() => {
+                if suffix == 0 || len == 0 {
+                    continue;
+                }
+                (len.saturating_sub(suffix), len - 1)
+            }
+            (Ok(start), Err(_)) if end.is_empty() => (start, len.saturating_sub(1)),
+            (Ok(start), Ok(end)) if start <= end => (start, end.min(len.saturating_sub(1))),
+            _ => return Ranges::Full,
+        };
+        if range.0 < len {
+            ranges.push(range);
+        }
+    }
+
+    let ranges = coalesce(ranges);
+    let whole = ranges.first() == Some(&(0, len.saturating_sub(1))) && ranges.len() == 1;
+    if parsed == 0 || whole || ranges.len() > MAX_RANGES {
+        Ranges::Full
+    } else if ranges.is_empty() {
+        Ranges::Unsatisfiable
+    } else {
+        Ranges::Partial(ranges)
+    }
+}
+
+fn coalesce(mut ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+async fn multipart(
+    path: &Path,
+    ranges: &[(u64, u64)],
+    len: u64,
+    mime: &Mime,
+) -> std::io::Result<Body> {
+    let boundary = format!("{:032x}", cynthia::utils::rander::u128(..));
+    let mut reader: Reader = Box::new(swap::empty());
+    let mut total = 0;
+
+    for (i, &(start, end)) in ranges.iter().enumerate() {
+        let head = format!(
+            "{}--{}\r\nContent-Type: {}\r\nContent-Range: bytes {}-{}/{}\r\n\r\n",
+            if i == 0 { "" } else { "\r\n" },
+            boundary,
+            mime,
+            start,
+            end,
+            len
+        );
+        let mut file = File::open(path).await?;
+        file.seek(SeekFrom::Start(start)).await?;
+        let part = swap::BufferReader::new(file.take(end - start + 1));
+
+        total += head.len() as u64 + end - start + 1;
+        reader = Box::new(
+            reader
+                .chain(swap::Cursor::new(head.into_bytes()))
+                .chain(part),
+        );
+    }
+
+    let tail = format!("\r\n--{}--\r\n", boundary);
+    total += tail.len() as u64;
+    reader = Box::new(reader.chain(swap::Cursor::new(tail.into_bytes())));
+
+    let mut body = Body::from_reader(reader, Some(total as usize));
+    body.set_mime(
+        format!("multipart/byteranges; boundary={}", boundary)
+            .parse::<Mime>()
+            .unwrap_or(mime::BYTE_STREAM),
+    );
+    Ok(body)
+}
@@ -0,0 +1,109 @@
+mod support;
+
+use cynthia::future::prelude::*;
+use cynthia::runtime;
+use nephele::http_types::{Request, Response, StatusCode};
+use nephele::proto::h1;
+
+async fn echo(mut req: Request) -> nephele::http_types::Result<Response> {
+    let body = req.body_string().await?;
+    let mut res = Response::new(StatusCode::Ok);
+    res.set_body(format!("{} {} {}", req.method(), req.url().path(), body));
+    Ok(res)
+}
+
+fn exchange(requests: &str) -> String {
+    runtime::block_on(async {
+        let (mut client, server) = support::duplex(64 * 1024);
+        let task = runtime::spawn(async move {
+            let res = h1::accept(server.clone(), echo).await;
+            server.close_write();
+            res
+        });
+
+        client.write_all(requests.as_bytes()).await.unwrap();
+        let mut responses = String::new();
+        client.read_to_string(&mut responses).await.unwrap();
+        task.await.unwrap();
+        responses
+    })
+}
+
+fn bodies(responses: &str) -> Vec<&str> {
+    responses
+        .split("HTTP/1.1 ")
+        .skip(1)
+        .map(|res| res.split("\r\n\r\n").nth(1).unwrap())
+        .collect()
+}
+
+#[test]
+fn pipelined_requests_in_one_write() {
+    let responses = exchange(concat!(
+        "GET /one HTTP/1.1\r\nHost: example.com\r\n\r\n",
+        "GET /two HTTP/1.1\r\nHost: example.com\r\n\r\n",
+        "GET /three HTTP/1.1\r\nHost: example.com\r\nConnection: close\r\n\r\n",
+    ));
+
+    assert_eq!(responses.matches("HTTP/1.1 200 OK").count(), 3);
+    assert_eq!(
+        bodies(&responses),
+        ["GET /one ", "GET /two ", "GET /three "]
+    );
+}
+
+#[test]
+fn pipelined_requests_with_bodies() {
+    let responses = exchange(concat!(
+        "POST /fixed HTTP/1.1\r\nHost: example.com\r\nContent-Length: 5\r\n\r\nhello",
+        "GET /between HTTP/1.1\r\nHost: example.com\r\n\r\n",
+        "POST /chunked HTTP/1.1\r\nHost: example.com\r\nTransfer-Encoding: chunked\r\n\r\n",
+        "5\r\nworld\r\n0\r\n\r\n",
+        "GET /last HTTP/1.1\r\nHost: example.com\r\nConnection: close\r\n\r\n",
+    ));
+
+    assert_eq!(responses.matches("HTTP/1.1 200 OK").count(), 4);
+    assert_eq!(
+        bodies(&responses),
+        [
+            "POST /fixed hello",
+            "GET /between ",
+            "POST /chunked world",
+            "GET /last ",
+        ]
+    );
+}
+
+#[test]
+fn pipelined_request_with_unread_body() {
+    let responses = runtime::block_on(async {
+        let (mut client, server) = support::duplex(64 * 1024);
+        let task = runtime::spawn(async move {
+            let res = h1::accept(server.clone(), |req: Request| async move {
+                let mut res = Response::new(StatusCode::Ok);
+                res.set_body(req.url().path().to_string());
+                Ok(res)
+            })
+            .await;
+            server.close_write();
+            res
+        });
+
+        client
+            .write_all(
+                concat!(
+                    "POST /skipped HTTP/1.1\r\nHost: example.com\r\nContent-Length: 5\r\n\r\nhello",
+                    "GET /next HTTP/1.1\r\nHost: example.com\r\nConnection: close\r\n\r\n",
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+        let mut responses = String::new();
+        client.read_to_string(&mut responses).await.unwrap();
+        task.await.unwrap();
+        responses
+    });
+
+    assert_eq!(bodies(&responses), ["/skipped", "/next"]);
+}
@@ -0,0 +1,113 @@
+mod support;
+
+use cynthia::future::prelude::*;
+use cynthia::future::timeout;
+use cynthia::runtime;
+use nephele::http_types::{Request, Response, StatusCode};
+use nephele::proto::h1::{self, ServerOptions};
+use std::io;
+use std::time::{Duration, Instant};
+
+const TIMEOUT: Duration = Duration::from_millis(100);
+const DEADLINE: Duration = Duration::from_secs(5);
+
+fn serve<F, Fut>(
+    server: support::Duplex,
+    opts: ServerOptions,
+    endpoint: F,
+) -> runtime::task::Task<nephele::http_types::Result<()>>
+where
+    F: Fn(Request) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = nephele::http_types::Result<Response>> + Send + 'static,
+{
+    runtime::spawn(async move {
+        let res = h1::accept_with_opts(server.clone(), endpoint, opts).await;
+        server.close_write();
+        res
+    })
+}
+
+async fn ok(_req: Request) -> nephele::http_types::Result<Response> {
+    Ok(Response::new(StatusCode::Ok))
+}
+
+#[test]
+fn keep_alive_timeout_closes_idle_connection() {
+    runtime::block_on(async {
+        let (mut client, server) = support::duplex(64 * 1024);
+        let opts = ServerOptions::default().keep_alive_timeout(Some(TIMEOUT));
+        let task = serve(server, opts, ok);
+
+        client
+            .write_all(b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n")
+            .await
+            .unwrap();
+
+        let start = Instant::now();
+        let mut responses = String::new();
+        timeout(DEADLINE, client.read_to_string(&mut responses))
+            .await
+            .expect("connection was not closed")
+            .unwrap();
+
+        assert!(start.elapsed() >= TIMEOUT);
+        assert!(responses.starts_with("HTTP/1.1 200 OK"));
+        assert_eq!(responses.matches("HTTP/1.1").count(), 1);
+        task.await.unwrap();
+    });
+}
+
+#[test]
+fn read_timeout_closes_connection_mid_body() {
+    runtime::block_on(async {
+        let (mut client, server) = support::duplex(64 * 1024);
+        let opts = ServerOptions::default().read_timeout(Some(TIMEOUT));
+        let task = serve(server, opts, |mut req: Request| async move {
+            req.body_string().await?;
+            Ok(Response::new(StatusCode::Ok))
+        });
+
+        client
+            .write_all(b"POST / HTTP/1.1\r\nHost: example.com\r\nContent-Length: 10\r\n\r\nhel")
+            .await
+            .unwrap();
+
+        let mut responses = String::new();
+        timeout(DEADLINE, client.read_to_string(&mut responses))
+            .await
+            .expect("connection was not closed")
+            .unwrap();
+
+        assert!(!responses.starts_with("HTTP/1.1 200"));
+        assert!(client.is_closed());
+        let _ = timeout(DEADLINE, task)
+            .await
+            .expect("server did not finish");
+    });
+}
+
+#[test]
+fn write_timeout_closes_connection_to_stalled_reader() {
+    runtime::block_on(async {
+        let (mut client, server) = support::duplex(1024);
+        let opts = ServerOptions::default().write_timeout(Some(TIMEOUT));
+        let task = serve(server, opts, |_req: Request| async move {
+            let mut res = Response::new(StatusCode::Ok);
+            res.set_body(vec![0u8; 1024 * 1024]);
+            Ok(res)
+        });
+
+        client
+            .write_all(b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n")
+            .await
+            .unwrap();
+
+        let err = timeout(DEADLINE, task)
+            .await
+            .expect("server did not give up on a stalled reader")
+            .unwrap_err();
+        let kind = err.downcast_ref::<io::Error>().map(io::Error::kind);
+        assert_eq!(kind, Some(io::ErrorKind::TimedOut));
+        assert!(client.is_closed());
+    });
+}
@@ -0,0 +1,113 @@
+#![allow(dead_code)]
+
+use cynthia::future::swap::{AsyncRead, AsyncWrite};
+use std::collections::VecDeque;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+#[derive(Clone)]
+pub struct Duplex {
+    read: Arc<Mutex<Pipe>>,
+    write: Arc<Mutex<Pipe>>,
+}
+
+#[derive(Default)]
+struct Pipe {
+    buf: VecDeque<u8>,
+    capacity: usize,
+    closed: bool,
+    read_waker: Option<Waker>,
+    write_waker: Option<Waker>,
+}
+
+pub fn duplex(capacity: usize) -> (Duplex, Duplex) {
+    let a = Arc::new(Mutex::new(Pipe {
+        capacity,
+        ..Pipe::default()
+    }));
+    let b = Arc::new(Mutex::new(Pipe {
+        capacity,
+        ..Pipe::default()
+    }));
+    let client = Duplex {
+        read: a.clone(),
+        write: b.clone(),
+    };
+    let server = Duplex { read: b, write: a };
+    (client, server)
+}
+
+impl Duplex {
+    pub fn is_closed(&self) -> bool {
+        self.read.lock().unwrap().closed
+    }
+
+    pub fn close_write(&self) {
+        let mut pipe = self.write.lock().unwrap();
+        pipe.closed = true;
+        if let Some(waker) = pipe.read_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl AsyncRead for Duplex {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut pipe = self.read.lock().unwrap();
+        if pipe.buf.is_empty() {
+            if pipe.closed {
+                return Poll::Ready(Ok(0));
+            }
+            pipe.read_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let n = buf.len().min(pipe.buf.len());
+        for (dst, src) in buf.iter_mut().zip(pipe.buf.drain(..n)) {
+            *dst = src;
+        }
+        if let Some(waker) = pipe.write_waker.take() {
+            waker.wake();
+        }
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl AsyncWrite for Duplex {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut pipe = self.write.lock().unwrap();
+        if pipe.closed {
+            return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()));
+        }
+
+        let n = buf.len().min(pipe.capacity - pipe.buf.len());
+        if n == 0 {
+            pipe.write_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        pipe.buf.extend(&buf[..n]);
+        if let Some(waker) = pipe.read_waker.take() {
+            waker.wake();
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.close_write();
+        Poll::Ready(Ok(()))
+    }
+}